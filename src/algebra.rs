@@ -1,9 +1,18 @@
 //! 代数的構造を表現するためのモジュール。
+pub mod act_add;
+pub mod act_affine;
+pub mod act_assign;
+pub mod act_flip;
+pub mod act_trivial;
 pub mod additive;
 pub mod minmax;
 pub mod multiplicative;
 pub mod op_add;
+pub mod op_gcd;
 pub mod op_max;
 pub mod op_min;
 pub mod op_mul;
+pub mod op_sized_add;
+pub mod op_wrapping_add;
+pub mod op_xor;
 pub mod structure;
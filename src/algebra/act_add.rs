@@ -0,0 +1,76 @@
+//! 加算による作用を表現するためのモジュール。
+//!
+//! `OpAdd<T>` を作用素のモノイドとして、
+//! - 区間加算・区間和 (`OpSizedAdd<T>`)
+//! - 区間加算・区間最小値 (`OpMin<T>`)
+//! - 区間加算・区間最大値 (`OpMax<T>`)
+//!
+//! を扱えるようにする。
+use cargo_snippet::snippet;
+
+use super::minmax::{Max, Min};
+use super::op_add::OpAdd;
+use super::op_max::OpMax;
+use super::op_min::OpMin;
+use super::op_sized_add::OpSizedAdd;
+use super::structure::MonoidAction;
+
+#[snippet("algebra/act_add")]
+macro_rules! act_add_int_impl {
+    ($($t:ty)*) => ($(
+        impl MonoidAction<OpSizedAdd<$t>> for OpAdd<$t> {
+            fn act(&self, x: ($t, usize), f: $t) -> ($t, usize) {
+                (x.0 + f * x.1 as $t, x.1)
+            }
+        }
+
+        impl MonoidAction<OpMin<$t>> for OpAdd<$t> {
+            fn act(&self, x: $t, f: $t) -> $t {
+                // 単位元は作用で動かさない
+                if x == <$t as Max>::max() {
+                    x
+                } else {
+                    x + f
+                }
+            }
+        }
+
+        impl MonoidAction<OpMax<$t>> for OpAdd<$t> {
+            fn act(&self, x: $t, f: $t) -> $t {
+                // 単位元は作用で動かさない
+                if x == <$t as Min>::min() {
+                    x
+                } else {
+                    x + f
+                }
+            }
+        }
+    )*)
+}
+
+#[snippet("algebra/act_add")]
+act_add_int_impl! { usize u8 u16 u32 u64 u128 isize i8 i16 i32 i64 i128 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_act_add() {
+        let op_add = OpAdd::<i64>::default();
+        assert_eq!(
+            MonoidAction::<OpSizedAdd<i64>>::act(&op_add, (10, 3), 2),
+            (16, 3)
+        );
+        assert_eq!(MonoidAction::<OpMin<i64>>::act(&op_add, 10, -2), 8);
+        assert_eq!(
+            MonoidAction::<OpMin<i64>>::act(&op_add, std::i64::MAX, -2),
+            std::i64::MAX
+        );
+        assert_eq!(MonoidAction::<OpMax<i64>>::act(&op_add, 10, -2), 8);
+        assert_eq!(
+            MonoidAction::<OpMax<i64>>::act(&op_add, std::i64::MIN, 2),
+            std::i64::MIN
+        );
+    }
+}
@@ -0,0 +1,96 @@
+//! アフィン変換による作用を表現するためのモジュール。
+use cargo_snippet::snippet;
+
+#[snippet("algebra/act_affine")]
+use std::marker::PhantomData;
+
+use super::additive::ClosedAdd;
+use super::multiplicative::ClosedMul;
+use super::op_sized_add::OpSizedAdd;
+use super::structure::{Associative, Identity, Magma, MonoidAction};
+
+/// アフィン変換 `x -> ax + b` を `(a, b)` で表し、その合成を表すための構造体
+///
+/// `op(f, g)` は `f` を適用した後に `g` を適用する変換を表す。
+#[snippet("algebra/act_affine")]
+pub struct ActAffine<T> {
+    phantom: PhantomData<T>,
+}
+
+#[snippet("algebra/act_affine")]
+impl<T> Default for ActAffine<T> {
+    fn default() -> Self {
+        Self {
+            phantom: PhantomData,
+        }
+    }
+}
+
+#[snippet("algebra/act_affine")]
+impl<T: Eq + ClosedAdd + ClosedMul + Copy> Magma for ActAffine<T> {
+    type Set = (T, T);
+
+    fn op(&self, f: Self::Set, g: Self::Set) -> Self::Set {
+        (f.0 * g.0, f.1 * g.0 + g.1)
+    }
+}
+
+#[snippet("algebra/act_affine")]
+impl<T: Eq + ClosedAdd + ClosedMul + Copy> Associative for ActAffine<T> {}
+
+#[snippet("algebra/act_affine")]
+macro_rules! act_affine_int_impl {
+    ($($t:ty)*) => ($(
+        impl Identity for ActAffine<$t> {
+            fn id(&self) -> Self::Set {
+                (1, 0)
+            }
+        }
+
+        impl MonoidAction<OpSizedAdd<$t>> for ActAffine<$t> {
+            fn act(&self, x: ($t, usize), f: ($t, $t)) -> ($t, usize) {
+                (f.0 * x.0 + f.1 * x.1 as $t, x.1)
+            }
+        }
+    )*)
+}
+
+#[snippet("algebra/act_affine")]
+act_affine_int_impl! { usize u8 u16 u32 u64 u128 isize i8 i16 i32 i64 i128 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::mod_int::{FiniteField, Modulo};
+
+    #[derive(Copy, Clone, Eq, PartialEq)]
+    struct Mod998244353;
+    impl Modulo for Mod998244353 {
+        fn modulo() -> i64 {
+            998244353
+        }
+    }
+    type F = FiniteField<Mod998244353>;
+
+    #[test]
+    fn test_act_affine() {
+        let act = ActAffine::<i64>::default();
+        assert_eq!(act.op((2, 3), (5, 7)), (10, 22));
+        assert_eq!(act.id(), (1, 0));
+        assert_eq!(act.act((10, 3), (2, 1)), (23, 3));
+    }
+
+    #[test]
+    fn test_act_affine_finite_field() {
+        let act = ActAffine::<F>::default();
+        assert_eq!(
+            act.op((F::new(2), F::new(3)), (F::new(5), F::new(7))),
+            (F::new(10), F::new(22))
+        );
+        assert_eq!(act.id(), (F::new(1), F::new(0)));
+        assert_eq!(
+            act.act((F::new(10), 3), (F::new(-1), F::new(1))),
+            (F::new(-7), 3)
+        );
+    }
+}
@@ -0,0 +1,75 @@
+//! 代入による作用を表現するためのモジュール。
+use cargo_snippet::snippet;
+
+#[snippet("algebra/act_assign")]
+use std::marker::PhantomData;
+
+use super::minmax::{Max, Min};
+use super::op_max::OpMax;
+use super::op_min::OpMin;
+use super::structure::{Associative, Identity, Magma, MonoidAction};
+
+/// 代入を表すための構造体
+///
+/// `None` は何もしないことを、`Some(v)` は `v` を代入することを表す。
+#[snippet("algebra/act_assign")]
+pub struct ActAssign<T> {
+    phantom: PhantomData<T>,
+}
+
+#[snippet("algebra/act_assign")]
+impl<T> Default for ActAssign<T> {
+    fn default() -> Self {
+        Self {
+            phantom: PhantomData,
+        }
+    }
+}
+
+#[snippet("algebra/act_assign")]
+impl<T: Eq> Magma for ActAssign<T> {
+    type Set = Option<T>;
+
+    fn op(&self, f: Self::Set, g: Self::Set) -> Self::Set {
+        g.or(f)
+    }
+}
+
+#[snippet("algebra/act_assign")]
+impl<T: Eq> Associative for ActAssign<T> {}
+
+#[snippet("algebra/act_assign")]
+impl<T: Eq> Identity for ActAssign<T> {
+    fn id(&self) -> Self::Set {
+        None
+    }
+}
+
+#[snippet("algebra/act_assign")]
+impl<T: Ord + Max> MonoidAction<OpMin<T>> for ActAssign<T> {
+    fn act(&self, x: T, f: Option<T>) -> T {
+        f.unwrap_or(x)
+    }
+}
+
+#[snippet("algebra/act_assign")]
+impl<T: Ord + Min> MonoidAction<OpMax<T>> for ActAssign<T> {
+    fn act(&self, x: T, f: Option<T>) -> T {
+        f.unwrap_or(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_act_assign() {
+        let act = ActAssign::<i32>::default();
+        assert_eq!(act.op(Some(1), Some(2)), Some(2));
+        assert_eq!(act.op(Some(1), None), Some(1));
+        assert_eq!(act.id(), None);
+        assert_eq!(MonoidAction::<OpMin<i32>>::act(&act, 3, Some(5)), 5);
+        assert_eq!(MonoidAction::<OpMax<i32>>::act(&act, 3, None), 3);
+    }
+}
@@ -0,0 +1,59 @@
+//! ビット反転による作用を表現するためのモジュール。
+use cargo_snippet::snippet;
+
+use super::op_sized_add::OpSizedAdd;
+use super::structure::{Associative, Commutative, Identity, Magma, MonoidAction};
+
+/// ビット反転を表すための構造体
+///
+/// `OpSizedAdd<usize>` の元 `(1 の個数, 区間の長さ)` に作用する。
+#[snippet("algebra/act_flip")]
+#[derive(Default)]
+pub struct ActFlip;
+
+#[snippet("algebra/act_flip")]
+impl Magma for ActFlip {
+    type Set = bool;
+
+    fn op(&self, f: Self::Set, g: Self::Set) -> Self::Set {
+        f ^ g
+    }
+}
+
+#[snippet("algebra/act_flip")]
+impl Associative for ActFlip {}
+
+#[snippet("algebra/act_flip")]
+impl Commutative for ActFlip {}
+
+#[snippet("algebra/act_flip")]
+impl Identity for ActFlip {
+    fn id(&self) -> Self::Set {
+        false
+    }
+}
+
+#[snippet("algebra/act_flip")]
+impl MonoidAction<OpSizedAdd<usize>> for ActFlip {
+    fn act(&self, x: (usize, usize), f: bool) -> (usize, usize) {
+        if f {
+            (x.1 - x.0, x.1)
+        } else {
+            x
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_act_flip() {
+        let act = ActFlip;
+        assert!(!act.op(true, true));
+        assert!(!act.id());
+        assert_eq!(act.act((2, 5), true), (3, 5));
+        assert_eq!(act.act((2, 5), false), (2, 5));
+    }
+}
@@ -0,0 +1,36 @@
+//! 自明な作用を表現するためのモジュール。
+use cargo_snippet::snippet;
+
+use super::structure::{Associative, Commutative, Identity, Magma, MonoidAction};
+
+/// 何もしない作用を表すための構造体
+///
+/// 区間作用を必要としないデータ構造で、作用のモノイドとして指定するために使う。
+#[snippet("algebra/act_trivial")]
+#[derive(Default)]
+pub struct ActTrivial;
+
+#[snippet("algebra/act_trivial")]
+impl Magma for ActTrivial {
+    type Set = ();
+
+    fn op(&self, _f: Self::Set, _g: Self::Set) -> Self::Set {}
+}
+
+#[snippet("algebra/act_trivial")]
+impl Associative for ActTrivial {}
+
+#[snippet("algebra/act_trivial")]
+impl Commutative for ActTrivial {}
+
+#[snippet("algebra/act_trivial")]
+impl Identity for ActTrivial {
+    fn id(&self) -> Self::Set {}
+}
+
+#[snippet("algebra/act_trivial")]
+impl<S: Magma> MonoidAction<S> for ActTrivial {
+    fn act(&self, x: S::Set, _f: Self::Set) -> S::Set {
+        x
+    }
+}
@@ -0,0 +1,67 @@
+//! 最大公約数の計算を型として表現するためのモジュール。
+use cargo_snippet::snippet;
+
+#[snippet("algebra/op_gcd")]
+use std::marker::PhantomData;
+
+use super::super::math::gcd::Gcd;
+use super::structure::{Associative, Commutative, Idempotent, Identity, Magma};
+
+/// 最大公約数の計算を表すための構造体
+#[snippet("algebra/op_gcd")]
+pub struct OpGcd<T> {
+    phantom: PhantomData<T>,
+}
+
+#[snippet("algebra/op_gcd")]
+impl<T> Default for OpGcd<T> {
+    fn default() -> Self {
+        Self {
+            phantom: PhantomData,
+        }
+    }
+}
+
+#[snippet("algebra/op_gcd")]
+impl<T: Eq + Gcd> Magma for OpGcd<T> {
+    type Set = T;
+
+    fn op(&self, x: Self::Set, y: Self::Set) -> Self::Set {
+        x.gcd(y)
+    }
+}
+
+#[snippet("algebra/op_gcd")]
+impl<T: Eq + Gcd> Associative for OpGcd<T> {}
+
+#[snippet("algebra/op_gcd")]
+impl<T: Eq + Gcd> Commutative for OpGcd<T> {}
+
+#[snippet("algebra/op_gcd")]
+impl<T: Eq + Gcd> Idempotent for OpGcd<T> {}
+
+#[snippet("algebra/op_gcd")]
+macro_rules! op_gcd_int_impl {
+    ($($t:ty)*) => ($(
+        impl Identity for OpGcd<$t> {
+            fn id(&self) -> Self::Set {
+                0
+            }
+        }
+    )*)
+}
+
+#[snippet("algebra/op_gcd")]
+op_gcd_int_impl! { usize u8 u16 u32 u64 u128 isize i8 i16 i32 i64 i128 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_op_gcd_magma() {
+        let op_gcd = OpGcd::<u64>::default();
+        assert_eq!(op_gcd.op(12, 18), 6);
+        assert_eq!(op_gcd.op(op_gcd.id(), 7), 7);
+    }
+}
@@ -5,7 +5,7 @@ use cargo_snippet::snippet;
 use std::marker::PhantomData;
 
 use super::minmax::Min;
-use super::structure::{Associative, Commutative, Identity, Magma};
+use super::structure::{Associative, Commutative, Idempotent, Identity, Magma};
 
 /// 最大値取得を表すための構造体
 #[snippet("algebra/op_max")]
@@ -37,6 +37,9 @@ impl<T: Ord> Associative for OpMax<T> {}
 #[snippet("algebra/op_max")]
 impl<T: Ord> Commutative for OpMax<T> {}
 
+#[snippet("algebra/op_max")]
+impl<T: Ord> Idempotent for OpMax<T> {}
+
 #[snippet("algebra/op_max")]
 impl<T> Identity for OpMax<T>
 where
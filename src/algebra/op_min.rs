@@ -2,7 +2,7 @@
 use cargo_snippet::snippet;
 
 use super::minmax::Max;
-use super::structure::{Associative, Commutative, Identity, Magma};
+use super::structure::{Associative, Commutative, Idempotent, Identity, Magma};
 
 #[snippet("algebra/op_min")]
 use std::marker::PhantomData;
@@ -37,6 +37,9 @@ impl<T: Ord> Associative for OpMin<T> {}
 #[snippet("algebra/op_min")]
 impl<T: Ord> Commutative for OpMin<T> {}
 
+#[snippet("algebra/op_min")]
+impl<T: Ord> Idempotent for OpMin<T> {}
+
 #[snippet("algebra/op_min")]
 impl<T> Identity for OpMin<T>
 where
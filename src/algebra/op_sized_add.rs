@@ -0,0 +1,64 @@
+//! 区間の長さを伴う加法演算を型として表現するためのモジュール。
+use cargo_snippet::snippet;
+
+#[snippet("algebra/op_sized_add")]
+use std::marker::PhantomData;
+
+use super::additive::ClosedAdd;
+use super::structure::{Associative, Commutative, Identity, Magma};
+
+/// `(和, 区間の長さ)` の組の加算を表すための構造体
+///
+/// 区間加算・区間アフィン変換などの作用で区間の長さが必要となる場合に使う。
+#[snippet("algebra/op_sized_add")]
+pub struct OpSizedAdd<T> {
+    phantom: PhantomData<T>,
+}
+
+#[snippet("algebra/op_sized_add")]
+impl<T> Default for OpSizedAdd<T> {
+    fn default() -> Self {
+        Self {
+            phantom: PhantomData,
+        }
+    }
+}
+
+#[snippet("algebra/op_sized_add")]
+impl<T: Eq + ClosedAdd> Magma for OpSizedAdd<T> {
+    type Set = (T, usize);
+
+    fn op(&self, x: Self::Set, y: Self::Set) -> Self::Set {
+        (x.0 + y.0, x.1 + y.1)
+    }
+}
+
+#[snippet("algebra/op_sized_add")]
+macro_rules! op_sized_add_int_impl {
+    ($($t:ty)*) => ($(
+        impl Associative for OpSizedAdd<$t> {}
+
+        impl Commutative for OpSizedAdd<$t> {}
+
+        impl Identity for OpSizedAdd<$t> {
+            fn id(&self) -> Self::Set {
+                (0, 0)
+            }
+        }
+    )*)
+}
+
+#[snippet("algebra/op_sized_add")]
+op_sized_add_int_impl! { usize u8 u16 u32 u64 u128 isize i8 i16 i32 i64 i128 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_op_sized_add_magma() {
+        let op = OpSizedAdd::<i64>::default();
+        assert_eq!(op.op((1, 1), (2, 3)), (3, 4));
+        assert_eq!(op.id(), (0, 0));
+    }
+}
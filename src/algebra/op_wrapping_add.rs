@@ -0,0 +1,70 @@
+//! オーバーフローを許す加法演算を型として表現するためのモジュール。
+use cargo_snippet::snippet;
+
+#[snippet("algebra/op_wrapping_add")]
+use std::marker::PhantomData;
+
+use super::structure::{Associative, Commutative, Identity, Magma, Recip};
+
+/// 整数の `2^N` を法とする加算を表すための構造体
+///
+/// 符号なし整数の和を可換群として扱うために用いる。
+#[snippet("algebra/op_wrapping_add")]
+pub struct OpWrappingAdd<T> {
+    phantom: PhantomData<T>,
+}
+
+#[snippet("algebra/op_wrapping_add")]
+impl<T> Default for OpWrappingAdd<T> {
+    fn default() -> Self {
+        Self {
+            phantom: PhantomData,
+        }
+    }
+}
+
+#[snippet("algebra/op_wrapping_add")]
+macro_rules! op_wrapping_add_int_impl {
+    ($($t:ty)*) => ($(
+        impl Magma for OpWrappingAdd<$t> {
+            type Set = $t;
+
+            fn op(&self, x: Self::Set, y: Self::Set) -> Self::Set {
+                x.wrapping_add(y)
+            }
+        }
+
+        impl Recip for OpWrappingAdd<$t> {
+            fn recip(&self, x: Self::Set) -> Self::Set {
+                x.wrapping_neg()
+            }
+        }
+
+        impl Associative for OpWrappingAdd<$t> {}
+
+        impl Commutative for OpWrappingAdd<$t> {}
+
+        impl Identity for OpWrappingAdd<$t> {
+            fn id(&self) -> Self::Set {
+                0
+            }
+        }
+    )*)
+}
+
+#[snippet("algebra/op_wrapping_add")]
+op_wrapping_add_int_impl! { usize u8 u16 u32 u64 u128 isize i8 i16 i32 i64 i128 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_op_wrapping_add_magma() {
+        let op = OpWrappingAdd::<usize>::default();
+        assert_eq!(op.op(5, op.recip(3)), 2);
+        assert_eq!(op.op(op.recip(3), 3), 0);
+        assert_eq!(op.op(std::usize::MAX, 1), 0);
+        assert_eq!(op.id(), 0);
+    }
+}
@@ -0,0 +1,73 @@
+//! 排他的論理和を型として表現するためのモジュール。
+use cargo_snippet::snippet;
+
+#[snippet("algebra/op_xor")]
+use std::marker::PhantomData;
+#[snippet("algebra/op_xor")]
+use std::ops::BitXor;
+
+use super::structure::{Associative, Commutative, Identity, Magma, Recip};
+
+/// 排他的論理和を表すための構造体
+#[snippet("algebra/op_xor")]
+pub struct OpXor<T> {
+    phantom: PhantomData<T>,
+}
+
+#[snippet("algebra/op_xor")]
+impl<T> Default for OpXor<T> {
+    fn default() -> Self {
+        Self {
+            phantom: PhantomData,
+        }
+    }
+}
+
+#[snippet("algebra/op_xor")]
+impl<T: Eq + BitXor<Output = T>> Magma for OpXor<T> {
+    type Set = T;
+
+    fn op(&self, x: Self::Set, y: Self::Set) -> Self::Set {
+        x ^ y
+    }
+}
+
+#[snippet("algebra/op_xor")]
+impl<T: Eq + BitXor<Output = T>> Recip for OpXor<T> {
+    fn recip(&self, x: Self::Set) -> Self::Set {
+        x
+    }
+}
+
+#[snippet("algebra/op_xor")]
+impl<T: Eq + BitXor<Output = T>> Associative for OpXor<T> {}
+
+#[snippet("algebra/op_xor")]
+impl<T: Eq + BitXor<Output = T>> Commutative for OpXor<T> {}
+
+#[snippet("algebra/op_xor")]
+macro_rules! op_xor_int_impl {
+    ($($t:ty)*) => ($(
+        impl Identity for OpXor<$t> {
+            fn id(&self) -> Self::Set {
+                0
+            }
+        }
+    )*)
+}
+
+#[snippet("algebra/op_xor")]
+op_xor_int_impl! { usize u8 u16 u32 u64 u128 isize i8 i16 i32 i64 i128 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_op_xor_magma() {
+        let op_xor = OpXor::default();
+        assert_eq!(op_xor.op(5, 3), 6);
+        assert_eq!(op_xor.recip(5), 5);
+        assert_eq!(op_xor.id(), 0);
+    }
+}
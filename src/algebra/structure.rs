@@ -29,6 +29,10 @@ pub trait Associative: Magma {}
 #[snippet("algebra/structure")]
 pub trait Commutative: Magma {}
 
+/// 冪等法則 (`op(x, x) == x`) を満たすことを示すトレイト。
+#[snippet("algebra/structure")]
+pub trait Idempotent: Magma {}
+
 /// 分配法則を満たすことを示すトレイト。
 #[snippet("algebra/structure")]
 pub trait Distributive<A: Magma> {}
@@ -59,6 +63,18 @@ impl<T: Monoid + Recip> Group for T {}
 pub trait CommutativeGroup: Group + Commutative {}
 impl<T: Group + Commutative> CommutativeGroup for T {}
 
+/// モノイド作用
+///
+/// モノイド `Self` の `S` の台集合への右作用 `x・f` を表すトレイト。
+/// 以下を満たすことを仮定する。
+/// - `act(act(x, f), g) == act(x, op(f, g))`
+/// - `act(x, id()) == x`
+/// - `S` がモノイドのとき、`act(S::op(x, y), f) == S::op(act(x, f), act(y, f))`
+#[snippet("algebra/structure")]
+pub trait MonoidAction<S: Magma>: Monoid {
+    fn act(&self, x: S::Set, f: Self::Set) -> S::Set;
+}
+
 /// 環
 #[snippet("algebra/structure")]
 pub trait Ring {}
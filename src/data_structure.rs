@@ -1,6 +1,24 @@
 //! データ構造に関連するモジュール。
+pub mod bit_vector;
+pub mod disjoint_sparse_table;
+pub mod dynamic_segtree;
 pub mod fenwick;
+pub mod fenwick_2d;
+pub mod implicit_treap;
 pub mod lazy_segtree;
+pub mod leftist_heap;
+pub mod link_cut_tree;
+pub mod partially_persistent_union_find;
+pub mod persistent_segtree;
+pub mod radix_heap;
+pub mod rollback_union_find;
 pub mod segtree;
+pub mod segtree_2d;
+pub mod segtree_beats;
+pub mod sparse_table;
+pub mod swag;
 pub mod traits;
+pub mod treap;
 pub mod union_find;
+pub mod wavelet_matrix;
+pub mod weighted_union_find;
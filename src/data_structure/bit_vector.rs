@@ -0,0 +1,145 @@
+//! 完備辞書。
+use cargo_snippet::snippet;
+
+/// 完備辞書
+///
+/// ビット列に対して、
+/// - `rank`: 区間 `[0, i)` に含まれる 0 / 1 の個数
+/// - `select`: `k` 番目の 0 / 1 の位置
+///
+/// をそれぞれ `O(1)`, `O(log N)` で計算することができる。
+#[snippet("data_structure/bit_vector")]
+#[derive(Debug, Clone)]
+pub struct BitVector {
+    len: usize,
+    words: Vec<u64>,
+    // 各ワードより前にある 1 の個数
+    cum: Vec<usize>,
+}
+
+#[snippet("data_structure/bit_vector")]
+impl BitVector {
+    /// ビット列の長さを返す。
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// ビット列が空かどうかを返す。
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// `i` 番目のビットを返す。
+    pub fn get(&self, i: usize) -> bool {
+        assert!(i < self.len);
+        self.words[i >> 6] >> (i & 63) & 1 == 1
+    }
+
+    /// 区間 `[0, i)` に含まれる 1 の個数を返す。
+    pub fn rank1(&self, i: usize) -> usize {
+        assert!(i <= self.len);
+        let mask = (1u64 << (i & 63)) - 1;
+        self.cum[i >> 6] + (self.words[i >> 6] & mask).count_ones() as usize
+    }
+
+    /// 区間 `[0, i)` に含まれる 0 の個数を返す。
+    pub fn rank0(&self, i: usize) -> usize {
+        i - self.rank1(i)
+    }
+
+    /// `k` 番目 (0-indexed) の 1 の位置を返す。
+    pub fn select1(&self, k: usize) -> Option<usize> {
+        self.select(true, k)
+    }
+
+    /// `k` 番目 (0-indexed) の 0 の位置を返す。
+    pub fn select0(&self, k: usize) -> Option<usize> {
+        self.select(false, k)
+    }
+
+    /// `k` 番目 (0-indexed) の `bit` の位置を返す。
+    fn select(&self, bit: bool, k: usize) -> Option<usize> {
+        // ワード w より前にある bit の個数
+        let count = |w: usize| {
+            if bit {
+                self.cum[w]
+            } else {
+                (w << 6) - self.cum[w]
+            }
+        };
+        let total = if bit {
+            self.rank1(self.len)
+        } else {
+            self.rank0(self.len)
+        };
+        if k >= total {
+            return None;
+        }
+        // count(w) <= k となる最大の w
+        let (mut lo, mut hi) = (0, self.words.len());
+        while hi - lo > 1 {
+            let mid = (lo + hi) / 2;
+            if count(mid) <= k {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        let mut k = k - count(lo);
+        for i in (lo << 6)..self.len {
+            if self.get(i) == bit {
+                if k == 0 {
+                    return Some(i);
+                }
+                k -= 1;
+            }
+        }
+        unreachable!()
+    }
+}
+
+#[snippet("data_structure/bit_vector")]
+impl From<Vec<bool>> for BitVector {
+    fn from(v: Vec<bool>) -> Self {
+        let len = v.len();
+        let mut words = vec![0u64; (len >> 6) + 1];
+        for (i, &b) in v.iter().enumerate() {
+            if b {
+                words[i >> 6] |= 1 << (i & 63);
+            }
+        }
+        let mut cum = vec![0; words.len()];
+        for i in 1..words.len() {
+            cum[i] = cum[i - 1] + words[i - 1].count_ones() as usize;
+        }
+        Self { len, words, cum }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bit_vector() {
+        for &n in &[0, 1, 63, 64, 65, 200] {
+            let v: Vec<bool> = (0..n).map(|i| (i * i + i / 3) % 3 == 0).collect();
+            let bv = BitVector::from(v.clone());
+            assert_eq!(bv.len(), n);
+            let mut ones = 0;
+            for (i, &b) in v.iter().enumerate() {
+                assert_eq!(bv.rank1(i), ones);
+                assert_eq!(bv.rank0(i), i - ones);
+                assert_eq!(bv.get(i), b);
+                ones += b as usize;
+            }
+            assert_eq!(bv.rank1(n), ones);
+            let pos1: Vec<_> = (0..n).filter(|&i| v[i]).collect();
+            let pos0: Vec<_> = (0..n).filter(|&i| !v[i]).collect();
+            for k in 0..=n {
+                assert_eq!(bv.select1(k), pos1.get(k).copied());
+                assert_eq!(bv.select0(k), pos0.get(k).copied());
+            }
+        }
+    }
+}
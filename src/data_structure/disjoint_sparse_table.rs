@@ -0,0 +1,136 @@
+//! Disjoint Sparse Table。
+use cargo_snippet::snippet;
+
+use super::super::algebra::structure::{Monoid, SemiGroup};
+use super::traits::Fold;
+
+#[snippet("data_structure/disjoint_sparse_table")]
+use std::ops::Range;
+
+/// Disjoint Sparse Table。
+///
+/// 半群に対して、静的な列の区間の要素の積の取得を
+/// 前計算 `O(N log N)`、クエリ `O(1)` で行うことができる。
+/// 半群に対しては空でない区間の積を `fold_nonempty` で取得する。
+/// モノイドに対しては `Fold` を実装し、空の区間の積は単位元となる。
+#[snippet("data_structure/disjoint_sparse_table")]
+pub struct DisjointSparseTable<S: SemiGroup> {
+    data: Vec<S::Set>,
+    // table[k][i]: 長さ 2^(k+1) のブロックの中央から i までの積
+    table: Vec<Vec<S::Set>>,
+    semigroup: S,
+}
+
+#[snippet("data_structure/disjoint_sparse_table")]
+impl<S> From<Vec<S::Set>> for DisjointSparseTable<S>
+where
+    S: SemiGroup,
+    S::Set: Clone,
+{
+    fn from(v: Vec<S::Set>) -> Self {
+        let semigroup = S::default();
+        let n = v.len();
+        let mut table = Vec::new();
+        let mut half = 1;
+        while half < n {
+            let mut row = v.clone();
+            for m in (half..n).step_by(half << 1) {
+                for i in (m - half..m - 1).rev() {
+                    row[i] = semigroup.op(v[i].clone(), row[i + 1].clone());
+                }
+                for i in m + 1..n.min(m + half) {
+                    row[i] = semigroup.op(row[i - 1].clone(), v[i].clone());
+                }
+            }
+            table.push(row);
+            half <<= 1;
+        }
+        Self {
+            data: v,
+            table,
+            semigroup,
+        }
+    }
+}
+
+#[snippet("data_structure/disjoint_sparse_table")]
+impl<S> DisjointSparseTable<S>
+where
+    S: SemiGroup,
+    S::Set: Clone,
+{
+    /// 空でない半開区間 `r` 上の積を計算する。
+    pub fn fold_nonempty(&self, r: Range<usize>) -> S::Set {
+        assert!(r.start < r.end, "empty range");
+        let (l, r) = (r.start, r.end - 1);
+        if l == r {
+            return self.data[l].clone();
+        }
+        let k = std::mem::size_of::<usize>() * 8 - (l ^ r).leading_zeros() as usize - 1;
+        self.semigroup
+            .op(self.table[k][l].clone(), self.table[k][r].clone())
+    }
+}
+
+#[snippet("data_structure/disjoint_sparse_table")]
+impl<M> Fold for DisjointSparseTable<M>
+where
+    M: Monoid,
+    M::Set: Clone,
+{
+    type Output = M::Set;
+
+    /// 半開区間上の積を計算する。空の区間に対しては単位元を返す。
+    fn fold(&self, r: Range<usize>) -> Self::Output {
+        if r.start >= r.end {
+            return self.semigroup.id();
+        }
+        self.fold_nonempty(r)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algebra::op_add::OpAdd;
+    use crate::algebra::structure::{Associative, Magma};
+
+    #[test]
+    fn test_disjoint_sparse_table_op_add() {
+        for n in 0..=33 {
+            let a: Vec<i64> = (0..n).map(|i| (i * 37 + 11) % 29 - 14).collect();
+            let dst = DisjointSparseTable::<OpAdd<i64>>::from(a.clone());
+            for l in 0..=n as usize {
+                for r in l..=n as usize {
+                    assert_eq!(dst.fold(l..r), a[l..r].iter().sum());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_disjoint_sparse_table_semigroup() {
+        // 文字列の連結 (単位元を持たない半群として扱う)
+        #[derive(Default)]
+        struct OpConcat;
+
+        impl Magma for OpConcat {
+            type Set = String;
+
+            fn op(&self, x: String, y: String) -> String {
+                x + &y
+            }
+        }
+
+        impl Associative for OpConcat {}
+
+        let s = "abcdefghijklmnopq";
+        let v: Vec<_> = s.chars().map(|c| c.to_string()).collect();
+        let dst = DisjointSparseTable::<OpConcat>::from(v);
+        for l in 0..s.len() {
+            for r in l + 1..=s.len() {
+                assert_eq!(dst.fold_nonempty(l..r), &s[l..r]);
+            }
+        }
+    }
+}
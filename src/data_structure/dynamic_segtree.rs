@@ -0,0 +1,357 @@
+//! 動的セグメント木。
+use cargo_snippet::snippet;
+
+use super::super::algebra::structure::Monoid;
+use super::traits::{BisectFold, BisectFoldRev, Fold, SetValue};
+
+#[snippet("data_structure/dynamic_segtree")]
+use std::ops::{Index, Range};
+
+#[snippet("data_structure/dynamic_segtree")]
+#[derive(Debug, Clone)]
+struct DynamicSegTreeNode<T> {
+    val: T,
+    left: Option<usize>,
+    right: Option<usize>,
+}
+
+/// 動的セグメント木。
+///
+/// 必要になったノードだけを作成するため、`10^18` 程度の大きなインデックスを
+/// 座標圧縮せずに扱うことができる。モノイドに対して、
+/// - 要素の一点更新
+/// - 区間の要素の積の取得
+///
+/// を `O(log N)` で行うことができる。
+#[snippet("data_structure/dynamic_segtree")]
+pub struct DynamicSegTree<M: Monoid> {
+    len: usize,
+    root: Option<usize>,
+    nodes: Vec<DynamicSegTreeNode<M::Set>>,
+    id: M::Set,
+    monoid: M,
+}
+
+#[snippet("data_structure/dynamic_segtree")]
+impl<M> DynamicSegTree<M>
+where
+    M: Monoid,
+    M::Set: Clone + Copy,
+{
+    /// インデックスが `0..n` の動的セグメント木を作成する。
+    pub fn new(n: usize) -> Self {
+        let monoid = M::default();
+        Self {
+            len: n,
+            root: None,
+            nodes: Vec::new(),
+            id: monoid.id(),
+            monoid,
+        }
+    }
+
+    fn val(&self, node: Option<usize>) -> M::Set {
+        match node {
+            Some(k) => self.nodes[k].val,
+            None => self.id,
+        }
+    }
+
+    fn set_rec(
+        &mut self,
+        node: Option<usize>,
+        lo: usize,
+        hi: usize,
+        i: usize,
+        val: M::Set,
+    ) -> usize {
+        let k = match node {
+            Some(k) => k,
+            None => {
+                self.nodes.push(DynamicSegTreeNode {
+                    val: self.id,
+                    left: None,
+                    right: None,
+                });
+                self.nodes.len() - 1
+            }
+        };
+        if hi - lo == 1 {
+            self.nodes[k].val = val;
+            return k;
+        }
+        let mid = lo + (hi - lo) / 2;
+        if i < mid {
+            let left = self.set_rec(self.nodes[k].left, lo, mid, i, val);
+            self.nodes[k].left = Some(left);
+        } else {
+            let right = self.set_rec(self.nodes[k].right, mid, hi, i, val);
+            self.nodes[k].right = Some(right);
+        }
+        let (left, right) = (self.nodes[k].left, self.nodes[k].right);
+        self.nodes[k].val = self.monoid.op(self.val(left), self.val(right));
+        k
+    }
+
+    fn get_rec(&self, node: Option<usize>, lo: usize, hi: usize, i: usize) -> &M::Set {
+        match node {
+            None => &self.id,
+            Some(k) if hi - lo == 1 => &self.nodes[k].val,
+            Some(k) => {
+                let mid = lo + (hi - lo) / 2;
+                if i < mid {
+                    self.get_rec(self.nodes[k].left, lo, mid, i)
+                } else {
+                    self.get_rec(self.nodes[k].right, mid, hi, i)
+                }
+            }
+        }
+    }
+
+    fn fold_rec(&self, node: Option<usize>, lo: usize, hi: usize, l: usize, r: usize) -> M::Set {
+        let k = match node {
+            Some(k) if l < hi && lo < r => k,
+            _ => return self.id,
+        };
+        if l <= lo && hi <= r {
+            return self.nodes[k].val;
+        }
+        let mid = lo + (hi - lo) / 2;
+        self.monoid.op(
+            self.fold_rec(self.nodes[k].left, lo, mid, l, r),
+            self.fold_rec(self.nodes[k].right, mid, hi, l, r),
+        )
+    }
+
+    /// `l` 以上の区間について `f` を満たさなくなる最初の位置を探す。
+    /// 見つからない場合は `None` を返す。
+    fn bisect_rec<F>(
+        &self,
+        node: Option<usize>,
+        lo: usize,
+        hi: usize,
+        l: usize,
+        f: &F,
+        acc: &mut M::Set,
+    ) -> Option<usize>
+    where
+        F: Fn(&M::Set) -> bool,
+    {
+        if hi <= l {
+            return None;
+        }
+        let k = node?;
+        if l <= lo {
+            let val = self.monoid.op(*acc, self.nodes[k].val);
+            if f(&val) {
+                *acc = val;
+                return None;
+            }
+            if hi - lo == 1 {
+                return Some(lo);
+            }
+        }
+        let mid = lo + (hi - lo) / 2;
+        self.bisect_rec(self.nodes[k].left, lo, mid, l, f, acc)
+            .or_else(|| self.bisect_rec(self.nodes[k].right, mid, hi, l, f, acc))
+    }
+
+    /// `r` 未満の区間について `f` を満たさなくなる最後の位置を探す。
+    /// 見つからない場合は `None` を返す。
+    fn bisect_rev_rec<F>(
+        &self,
+        node: Option<usize>,
+        lo: usize,
+        hi: usize,
+        r: usize,
+        f: &F,
+        acc: &mut M::Set,
+    ) -> Option<usize>
+    where
+        F: Fn(&M::Set) -> bool,
+    {
+        if r <= lo {
+            return None;
+        }
+        let k = node?;
+        if hi <= r {
+            let val = self.monoid.op(self.nodes[k].val, *acc);
+            if f(&val) {
+                *acc = val;
+                return None;
+            }
+            if hi - lo == 1 {
+                return Some(hi);
+            }
+        }
+        let mid = lo + (hi - lo) / 2;
+        self.bisect_rev_rec(self.nodes[k].right, mid, hi, r, f, acc)
+            .or_else(|| self.bisect_rev_rec(self.nodes[k].left, lo, mid, r, f, acc))
+    }
+}
+
+#[snippet("data_structure/dynamic_segtree")]
+impl<M> SetValue<M::Set> for DynamicSegTree<M>
+where
+    M: Monoid,
+    M::Set: Clone + Copy,
+{
+    fn set(&mut self, i: usize, val: M::Set) {
+        assert!(i < self.len);
+        let root = self.set_rec(self.root, 0, self.len, i, val);
+        self.root = Some(root);
+    }
+}
+
+#[snippet("data_structure/dynamic_segtree")]
+impl<M> Index<usize> for DynamicSegTree<M>
+where
+    M: Monoid,
+    M::Set: Clone + Copy,
+{
+    type Output = M::Set;
+
+    fn index(&self, i: usize) -> &Self::Output {
+        assert!(i < self.len);
+        self.get_rec(self.root, 0, self.len, i)
+    }
+}
+
+#[snippet("data_structure/dynamic_segtree")]
+impl<M> Fold for DynamicSegTree<M>
+where
+    M: Monoid,
+    M::Set: Clone + Copy,
+{
+    type Output = M::Set;
+
+    fn fold(&self, r: Range<usize>) -> Self::Output {
+        self.fold_rec(self.root, 0, self.len, r.start, r.end)
+    }
+}
+
+#[snippet("data_structure/dynamic_segtree")]
+impl<M> BisectFold<M::Set> for DynamicSegTree<M>
+where
+    M: Monoid,
+    M::Set: Clone + Copy,
+{
+    fn bisect_fold<F>(&self, l: usize, f: F) -> usize
+    where
+        F: Fn(&M::Set) -> bool,
+    {
+        let mut acc = self.id;
+        self.bisect_rec(self.root, 0, self.len, l, &f, &mut acc)
+            .unwrap_or(self.len)
+    }
+}
+
+#[snippet("data_structure/dynamic_segtree")]
+impl<M> BisectFoldRev<M::Set> for DynamicSegTree<M>
+where
+    M: Monoid,
+    M::Set: Clone + Copy,
+{
+    fn bisect_fold_rev<F>(&self, r: usize, f: F) -> usize
+    where
+        F: Fn(&M::Set) -> bool,
+    {
+        let mut acc = self.id;
+        self.bisect_rev_rec(self.root, 0, self.len, r, &f, &mut acc)
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algebra::op_add::OpAdd;
+    use crate::algebra::op_min::OpMin;
+
+    use super::*;
+
+    #[test]
+    fn test_dynamic_segtree_op_add() {
+        let mut seg = DynamicSegTree::<OpAdd<usize>>::new(5);
+
+        for i in 0..5 {
+            seg.set(i, i * i);
+        }
+
+        assert_eq!(seg[0], 0);
+        assert_eq!(seg[2], 4);
+
+        assert_eq!(seg.fold(0..0), 0);
+        assert_eq!(seg.fold(0..3), 5);
+        assert_eq!(seg.fold(1..3), 5);
+
+        assert_eq!(seg.bisect_fold(0, |&x| x <= 5), 3);
+        assert_eq!(seg.bisect_fold(0, |&x| x < 5), 2);
+
+        assert_eq!(seg.bisect_fold_rev(5, |&x| x <= 25), 3);
+        assert_eq!(seg.bisect_fold_rev(5, |&x| x < 25), 4);
+    }
+
+    #[test]
+    fn test_dynamic_segtree_large_index() {
+        let n = 1_000_000_000_000_000_000;
+        let mut seg = DynamicSegTree::<OpMin<i64>>::new(n);
+        seg.set(10, 5);
+        seg.set(n / 2, 3);
+        seg.set(n - 1, 1);
+
+        assert_eq!(seg[11], std::i64::MAX);
+        assert_eq!(seg[n / 2], 3);
+        assert_eq!(seg.fold(0..n), 1);
+        assert_eq!(seg.fold(0..n / 2), 5);
+        assert_eq!(seg.fold(11..n / 2), std::i64::MAX);
+        assert_eq!(seg.fold(11..n - 1), 3);
+
+        assert_eq!(seg.bisect_fold(0, |&x| x > 3), n / 2);
+        assert_eq!(seg.bisect_fold(n / 2 + 1, |&x| x > 0), n);
+        assert_eq!(seg.bisect_fold_rev(n, |&x| x > 0), 0);
+        assert_eq!(seg.bisect_fold_rev(n - 1, |&x| x > 3), n / 2 + 1);
+        assert_eq!(seg.bisect_fold_rev(n / 2, |&x| x > 0), 0);
+    }
+
+    #[test]
+    fn test_dynamic_segtree_naive() {
+        for n in 1..=20 {
+            let mut seg = DynamicSegTree::<OpAdd<usize>>::new(n);
+            let mut v = vec![0; n];
+            for i in (0..n).step_by(3) {
+                seg.set(i, i + 1);
+                v[i] = i + 1;
+            }
+            for l in 0..=n {
+                for r in l..=n {
+                    let s: usize = v[l..r].iter().sum();
+                    assert_eq!(seg.fold(l..r), s);
+                }
+                for w in 0..30 {
+                    let mut r = l;
+                    let mut s = 0;
+                    while r < n && s + v[r] <= w {
+                        s += v[r];
+                        r += 1;
+                    }
+                    assert_eq!(seg.bisect_fold(l, |&x| x <= w), r);
+
+                    let mut k = l;
+                    let mut s = 0;
+                    while k > 0 && s + v[k - 1] <= w {
+                        s += v[k - 1];
+                        k -= 1;
+                    }
+                    assert_eq!(seg.bisect_fold_rev(l, |&x| x <= w), k);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_dynamic_segtree_set_out_of_range() {
+        let mut seg = DynamicSegTree::<OpAdd<i64>>::new(5);
+        seg.set(5, 1);
+    }
+}
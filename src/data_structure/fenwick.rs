@@ -4,49 +4,59 @@ use cargo_snippet::snippet;
 #[snippet("data_structure/fenwick")]
 use std::fmt::Debug;
 #[snippet("data_structure/fenwick")]
-use std::ops::{AddAssign, Range, Sub};
+use std::ops::Range;
 
-use super::traits::Fold;
+use super::super::algebra::op_add::OpAdd;
+use super::super::algebra::structure::{Commutative, CommutativeGroup, Monoid};
+use super::traits::{BisectFold, Fold};
 
 /// フェニック木。
 /// * 一点加算
 /// * 区間取得
+///
 /// の二つのクエリを `O(log N)` で処理できるデータ構造
+///
+/// 可換モノイドに対しては一点加算と接頭辞の積の取得を、
+/// 可換群に対してはさらに区間の積の取得を行うことができる。
+/// 符号なし整数の和の区間取得には `OpWrappingAdd` を用いる。
 #[snippet("data_structure/fenwick")]
-pub struct FenwickTree<T> {
+pub struct FenwickTree<M: Monoid> {
     len: usize,
-    data: Vec<T>,
-    e: T,
+    data: Vec<M::Set>,
+    monoid: M,
 }
 
 #[snippet("data_structure/fenwick")]
-impl<T> FenwickTree<T>
+impl<M> FenwickTree<M>
 where
-    T: Copy + Clone + AddAssign<T> + Sub<Output = T>,
+    M: Monoid + Commutative,
+    M::Set: Clone + Copy,
 {
     /// 長さ n のフェニック木を作成する。
-    pub fn new(n: usize, e: T) -> Self {
+    pub fn new(n: usize) -> Self {
+        let monoid = M::default();
         Self {
             len: n,
-            data: vec![e; n],
-            e,
+            data: vec![monoid.id(); n],
+            monoid,
         }
     }
 
-    /// `a[i] += val` という更新を行う。
-    pub fn add(&mut self, i: usize, val: T) {
+    /// `a[i] = op(a[i], val)` という更新を行う。
+    pub fn add(&mut self, i: usize, val: M::Set) {
         let mut i = i + 1;
         while i <= self.len {
-            self.data[i - 1] += val;
+            self.data[i - 1] = self.monoid.op(self.data[i - 1], val);
             i += i & (!i + 1);
         }
     }
 
-    pub fn prefix_sum(&self, r: usize) -> T {
-        let mut s = self.e;
+    /// 半開区間 `[0, r)` 上の積を計算する。
+    pub fn prefix_sum(&self, r: usize) -> M::Set {
+        let mut s = self.monoid.id();
         let mut idx = r;
         while idx > 0 {
-            s += self.data[idx - 1];
+            s = self.monoid.op(s, self.data[idx - 1]);
             idx -= idx & (!idx + 1);
         }
         s
@@ -54,22 +64,117 @@ where
 }
 
 #[snippet("data_structure/fenwick")]
-impl<T> Fold for FenwickTree<T>
+impl<G> Fold for FenwickTree<G>
 where
-    T: Copy + Clone + AddAssign<T> + Sub<Output = T>,
+    G: CommutativeGroup,
+    G::Set: Clone + Copy,
 {
-    type Output = T;
+    type Output = G::Set;
 
     /// 半開区間上の和を計算する。
-    fn fold(&self, r: Range<usize>) -> T {
-        self.prefix_sum(r.end) - self.prefix_sum(r.start)
+    fn fold(&self, r: Range<usize>) -> G::Set {
+        self.monoid.op(
+            self.prefix_sum(r.end),
+            self.monoid.recip(self.prefix_sum(r.start)),
+        )
     }
 }
 
 #[snippet("data_structure/fenwick")]
-impl<T> Debug for FenwickTree<T>
+impl<G> BisectFold<G::Set> for FenwickTree<G>
 where
-    T: std::fmt::Debug + Copy + Clone + AddAssign<T> + Sub<Output = T>,
+    G: CommutativeGroup,
+    G::Set: Clone + Copy,
+{
+    /// `f(fold(l..r))` を満たす最大の `r` を二分探索 (binary lifting) で求める。
+    ///
+    /// 例えば非負の値を持つとき、`bisect_fold(0, |&s| s < w)` は
+    /// `prefix_sum(r + 1) >= w` となる最小の `r` を返す。
+    fn bisect_fold<F>(&self, l: usize, f: F) -> usize
+    where
+        F: Fn(&G::Set) -> bool,
+    {
+        let mut pos = 0;
+        let mut acc = self.monoid.recip(self.prefix_sum(l));
+        let mut k = 1;
+        while k << 1 <= self.len {
+            k <<= 1;
+        }
+        while k > 0 {
+            if pos + k <= self.len {
+                let val = self.monoid.op(acc, self.data[pos + k - 1]);
+                // l 以下の位置へは無条件に進む
+                if pos + k <= l || f(&val) {
+                    pos += k;
+                    acc = val;
+                }
+            }
+            k >>= 1;
+        }
+        pos
+    }
+}
+
+/// 区間加算に対応したフェニック木。
+/// * 区間加算
+/// * 区間取得
+///
+/// の二つのクエリを `O(log N)` で処理できるデータ構造
+#[snippet("data_structure/fenwick")]
+pub struct RangeAddFenwickTree<T>
+where
+    OpAdd<T>: Monoid,
+{
+    // 区間 [0, i) の和は i * b0.prefix_sum(i) + b1.prefix_sum(i) で表される
+    b0: FenwickTree<OpAdd<T>>,
+    b1: FenwickTree<OpAdd<T>>,
+}
+
+#[snippet("data_structure/fenwick")]
+macro_rules! range_add_fenwick_impl {
+    ($($t:ty)*) => ($(
+        impl RangeAddFenwickTree<$t> {
+            /// 長さ n のフェニック木を作成する。
+            pub fn new(n: usize) -> Self {
+                Self {
+                    b0: FenwickTree::new(n + 1),
+                    b1: FenwickTree::new(n + 1),
+                }
+            }
+
+            /// 半開区間 `r` の各要素に `val` を加算する。
+            pub fn add(&mut self, r: Range<usize>, val: $t) {
+                self.b0.add(r.start, val);
+                self.b0.add(r.end, -val);
+                self.b1.add(r.start, -val * r.start as $t);
+                self.b1.add(r.end, val * r.end as $t);
+            }
+
+            /// 半開区間 `[0, r)` 上の和を計算する。
+            pub fn prefix_sum(&self, r: usize) -> $t {
+                self.b0.prefix_sum(r) * r as $t + self.b1.prefix_sum(r)
+            }
+        }
+
+        impl Fold for RangeAddFenwickTree<$t> {
+            type Output = $t;
+
+            /// 半開区間上の和を計算する。
+            fn fold(&self, r: Range<usize>) -> $t {
+                self.prefix_sum(r.end) - self.prefix_sum(r.start)
+            }
+        }
+    )*)
+}
+
+#[snippet("data_structure/fenwick")]
+range_add_fenwick_impl! { isize i8 i16 i32 i64 i128 }
+
+#[snippet("data_structure/fenwick")]
+impl<M> Debug for FenwickTree<M>
+where
+    M: Monoid,
+    M::Set: Debug,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_list().entries(self.data.iter()).finish()
@@ -79,10 +184,16 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::algebra::op_add::OpAdd;
+    use crate::algebra::op_max::OpMax;
+    use crate::algebra::op_mul::OpMul;
+    use crate::algebra::op_wrapping_add::OpWrappingAdd;
+    use crate::algebra::op_xor::OpXor;
+    use crate::math::mod_int::{FiniteField, Modulo};
 
     #[test]
     fn test_fenwick_basic() {
-        let mut fenwick = FenwickTree::new(5, 0);
+        let mut fenwick = FenwickTree::<OpWrappingAdd<usize>>::new(5);
         for i in 0..5 {
             fenwick.add(i, i);
         }
@@ -95,7 +206,7 @@ mod tests {
     #[test]
     fn test_fenwick_square() {
         for n in 0..=50 {
-            let mut fenwick = FenwickTree::new(n, 0);
+            let mut fenwick = FenwickTree::<OpWrappingAdd<usize>>::new(n);
             for i in 0..n {
                 fenwick.add(i, i * i);
             }
@@ -110,4 +221,117 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_fenwick_xor() {
+        let a = [3u32, 5, 6, 9, 12, 7];
+        let mut fenwick = FenwickTree::<OpXor<u32>>::new(a.len());
+        for (i, &x) in a.iter().enumerate() {
+            fenwick.add(i, x);
+        }
+        for l in 0..=a.len() {
+            for r in l..=a.len() {
+                assert_eq!(fenwick.fold(l..r), a[l..r].iter().fold(0, |s, &x| s ^ x));
+            }
+        }
+    }
+
+    #[test]
+    fn test_fenwick_finite_field_mul() {
+        #[derive(Copy, Clone, Eq, PartialEq)]
+        struct Mod1000000007;
+        impl Modulo for Mod1000000007 {
+            fn modulo() -> i64 {
+                1000000007
+            }
+        }
+        type F = FiniteField<Mod1000000007>;
+
+        let a: Vec<_> = (1..=8).map(|x| F::new(x * 3)).collect();
+        let mut fenwick = FenwickTree::<OpMul<F>>::new(a.len());
+        for (i, &x) in a.iter().enumerate() {
+            fenwick.add(i, x);
+        }
+        for l in 0..=a.len() {
+            for r in l..=a.len() {
+                let p = a[l..r].iter().fold(F::new(1), |p, &x| p * x);
+                assert_eq!(fenwick.fold(l..r), p);
+            }
+        }
+    }
+
+    #[test]
+    fn test_fenwick_prefix_max() {
+        let mut fenwick = FenwickTree::<OpMax<i32>>::new(5);
+        assert_eq!(fenwick.prefix_sum(5), std::i32::MIN);
+        fenwick.add(3, 4);
+        fenwick.add(1, 2);
+        assert_eq!(fenwick.prefix_sum(1), std::i32::MIN);
+        assert_eq!(fenwick.prefix_sum(3), 2);
+        assert_eq!(fenwick.prefix_sum(5), 4);
+        fenwick.add(1, 5);
+        assert_eq!(fenwick.prefix_sum(2), 5);
+        assert_eq!(fenwick.prefix_sum(5), 5);
+    }
+
+    #[test]
+    fn test_fenwick_bisect_fold() {
+        for n in 0..=30 {
+            let mut fenwick = FenwickTree::<OpAdd<i64>>::new(n);
+            let a: Vec<i64> = (0..n).map(|i| ((i * 7) % 5) as i64).collect();
+            for (i, &x) in a.iter().enumerate() {
+                fenwick.add(i, x);
+            }
+            for l in 0..=n {
+                for w in 0..40 {
+                    let mut r = l;
+                    let mut s = 0;
+                    while r < n && s + a[r] <= w {
+                        s += a[r];
+                        r += 1;
+                    }
+                    assert_eq!(fenwick.bisect_fold(l, |&x| x <= w), r);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_fenwick_order_statistics() {
+        // 多重集合 {1, 1, 3, 4, 4, 4} の k 番目に小さい値
+        let mut fenwick = FenwickTree::<OpAdd<i32>>::new(6);
+        for &x in &[1, 4, 3, 4, 1, 4] {
+            fenwick.add(x, 1);
+        }
+        let kth = |k: i32| fenwick.bisect_fold(0, |&s| s <= k);
+        assert_eq!(kth(0), 1);
+        assert_eq!(kth(1), 1);
+        assert_eq!(kth(2), 3);
+        assert_eq!(kth(3), 4);
+        assert_eq!(kth(5), 4);
+        assert_eq!(kth(6), 6);
+    }
+
+    #[test]
+    fn test_range_add_fenwick_square() {
+        for n in 0..=30 {
+            let mut fenwick = RangeAddFenwickTree::<i64>::new(n);
+            let mut a = vec![0; n];
+            for i in 0..n {
+                let (l, r) = ((i * 7) % (n + 1), (i * 11) % (n + 1));
+                let (l, r) = (l.min(r), l.max(r));
+                let val = (i * i) as i64 - 10;
+                fenwick.add(l..r, val);
+                for x in &mut a[l..r] {
+                    *x += val;
+                }
+            }
+            for l in 0..=n {
+                for r in l..=n {
+                    let s: i64 = a[l..r].iter().sum();
+                    assert_eq!(fenwick.fold(l..r), s);
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,126 @@
+//! 二次元フェニック木。
+use cargo_snippet::snippet;
+
+#[snippet("data_structure/fenwick_2d")]
+use std::ops::Range;
+
+use super::super::algebra::structure::CommutativeGroup;
+use super::traits::Fold2D;
+
+/// 二次元フェニック木。
+/// * 一点加算
+/// * 矩形領域の和の取得
+///
+/// の二つのクエリを `O(log H log W)` で処理できるデータ構造
+#[snippet("data_structure/fenwick_2d")]
+pub struct FenwickTree2D<G: CommutativeGroup> {
+    h: usize,
+    w: usize,
+    data: Vec<Vec<G::Set>>,
+    group: G,
+}
+
+#[snippet("data_structure/fenwick_2d")]
+impl<G> FenwickTree2D<G>
+where
+    G: CommutativeGroup,
+    G::Set: Clone + Copy,
+{
+    /// 大きさ `h` × `w` の二次元フェニック木を作成する。
+    pub fn new(h: usize, w: usize) -> Self {
+        let group = G::default();
+        Self {
+            h,
+            w,
+            data: vec![vec![group.id(); w]; h],
+            group,
+        }
+    }
+
+    /// `a[x][y] += val` という更新を行う。
+    pub fn add(&mut self, x: usize, y: usize, val: G::Set) {
+        let mut i = x + 1;
+        while i <= self.h {
+            let mut j = y + 1;
+            while j <= self.w {
+                self.data[i - 1][j - 1] = self.group.op(self.data[i - 1][j - 1], val);
+                j += j & (!j + 1);
+            }
+            i += i & (!i + 1);
+        }
+    }
+
+    /// `[0, x) × [0, y)` 上の和を計算する。
+    pub fn prefix_sum(&self, x: usize, y: usize) -> G::Set {
+        let mut s = self.group.id();
+        let mut i = x;
+        while i > 0 {
+            let mut j = y;
+            while j > 0 {
+                s = self.group.op(s, self.data[i - 1][j - 1]);
+                j -= j & (!j + 1);
+            }
+            i -= i & (!i + 1);
+        }
+        s
+    }
+}
+
+#[snippet("data_structure/fenwick_2d")]
+impl<G> Fold2D for FenwickTree2D<G>
+where
+    G: CommutativeGroup,
+    G::Set: Clone + Copy,
+{
+    type Output = G::Set;
+
+    /// 矩形領域上の和を計算する。
+    fn fold(&self, rx: Range<usize>, ry: Range<usize>) -> G::Set {
+        let g = &self.group;
+        let pos = g.op(
+            self.prefix_sum(rx.end, ry.end),
+            self.prefix_sum(rx.start, ry.start),
+        );
+        let neg = g.op(
+            self.prefix_sum(rx.start, ry.end),
+            self.prefix_sum(rx.end, ry.start),
+        );
+        g.op(pos, g.recip(neg))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algebra::op_add::OpAdd;
+
+    #[test]
+    fn test_fenwick_2d() {
+        let (h, w) = (6, 5);
+        let mut fenwick = FenwickTree2D::<OpAdd<i64>>::new(h, w);
+        let mut grid = vec![vec![0; w]; h];
+        for (x, row) in grid.iter_mut().enumerate() {
+            for (y, cell) in row.iter_mut().enumerate() {
+                let v = ((x * 7 + y * 3) % 11) as i64 - 5;
+                fenwick.add(x, y, v);
+                *cell += v;
+            }
+        }
+        fenwick.add(2, 3, 100);
+        grid[2][3] += 100;
+
+        for x0 in 0..=h {
+            for x1 in x0..=h {
+                for y0 in 0..=w {
+                    for y1 in y0..=w {
+                        let s: i64 = grid[x0..x1]
+                            .iter()
+                            .map(|row| row[y0..y1].iter().sum::<i64>())
+                            .sum();
+                        assert_eq!(fenwick.fold(x0..x1, y0..y1), s);
+                    }
+                }
+            }
+        }
+    }
+}
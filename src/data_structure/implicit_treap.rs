@@ -0,0 +1,222 @@
+//! 暗黙のキーを持つ Treap。
+use cargo_snippet::snippet;
+
+#[snippet("data_structure/implicit_treap")]
+use std::ops::Range;
+
+use super::super::algebra::structure::{Monoid, MonoidAction};
+use super::traits::{Fold, SetValue};
+use super::treap::Treap;
+
+/// 暗黙のキーを持つ Treap。
+///
+/// 要素の位置をキーとして扱うことで、列に対して
+/// - 任意の位置への挿入・削除
+/// - 一点更新
+/// - 区間の積の取得・区間作用・区間反転
+///
+/// を期待計算量 `O(log N)` で行うことができる。
+#[snippet("data_structure/implicit_treap")]
+pub struct ImplicitTreap<M: Monoid, X: MonoidAction<M>> {
+    treap: Treap<(), M, X>,
+}
+
+#[snippet("data_structure/implicit_treap")]
+impl<M, X> Default for ImplicitTreap<M, X>
+where
+    M: Monoid,
+    M::Set: Clone,
+    X: MonoidAction<M>,
+    X::Set: Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[snippet("data_structure/implicit_treap")]
+impl<M, X> ImplicitTreap<M, X>
+where
+    M: Monoid,
+    M::Set: Clone,
+    X: MonoidAction<M>,
+    X::Set: Clone,
+{
+    /// 空の列を作成する。
+    pub fn new() -> Self {
+        Self {
+            treap: Treap::new(),
+        }
+    }
+
+    /// 列の長さを返す。
+    pub fn len(&self) -> usize {
+        self.treap.len()
+    }
+
+    /// 列が空かどうかを返す。
+    pub fn is_empty(&self) -> bool {
+        self.treap.is_empty()
+    }
+
+    /// `i` 番目の要素を返す。
+    pub fn get(&self, i: usize) -> Option<M::Set> {
+        self.treap.get(i)
+    }
+
+    /// 位置 `i` に `val` を挿入する。
+    pub fn insert(&mut self, i: usize, val: M::Set) {
+        self.treap.insert_at(i, (), val);
+    }
+
+    /// 位置 `i` の要素を削除して返す。
+    pub fn remove(&mut self, i: usize) -> Option<M::Set> {
+        self.treap.remove_at(i).map(|(_, val)| val)
+    }
+
+    /// 半開区間 `r` の各要素に `f` を作用させる。
+    pub fn apply(&mut self, r: Range<usize>, f: X::Set) {
+        self.treap.apply(r, f);
+    }
+
+    /// 半開区間 `r` の要素の並びを反転する。
+    pub fn reverse(&mut self, r: Range<usize>) {
+        self.treap.reverse(r);
+    }
+}
+
+#[snippet("data_structure/implicit_treap")]
+impl<M, X> From<Vec<M::Set>> for ImplicitTreap<M, X>
+where
+    M: Monoid,
+    M::Set: Clone,
+    X: MonoidAction<M>,
+    X::Set: Clone,
+{
+    fn from(v: Vec<M::Set>) -> Self {
+        let mut treap = Self::new();
+        for (i, val) in v.into_iter().enumerate() {
+            treap.insert(i, val);
+        }
+        treap
+    }
+}
+
+#[snippet("data_structure/implicit_treap")]
+impl<M, X> SetValue<M::Set> for ImplicitTreap<M, X>
+where
+    M: Monoid,
+    M::Set: Clone,
+    X: MonoidAction<M>,
+    X::Set: Clone,
+{
+    fn set(&mut self, index: usize, val: M::Set) {
+        self.treap.set_at(index, val);
+    }
+}
+
+#[snippet("data_structure/implicit_treap")]
+impl<M, X> Fold for ImplicitTreap<M, X>
+where
+    M: Monoid,
+    M::Set: Clone,
+    X: MonoidAction<M>,
+    X::Set: Clone,
+{
+    type Output = M::Set;
+
+    /// 半開区間上の積を計算する。
+    fn fold(&self, r: Range<usize>) -> M::Set {
+        self.treap.fold(r)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algebra::act_trivial::ActTrivial;
+    use crate::algebra::op_add::OpAdd;
+    use crate::algebra::op_min::OpMin;
+    use crate::data_structure::segtree::SegTree;
+    use crate::utils::test_rng::XorShift;
+
+    #[test]
+    fn test_implicit_treap_basic() {
+        let mut treap = ImplicitTreap::<OpAdd<i64>, ActTrivial>::from(vec![1, 2, 3, 4, 5]);
+        assert_eq!(treap.fold(0..5), 15);
+        treap.insert(2, 10);
+        assert_eq!(treap.fold(0..3), 13);
+        assert_eq!(treap.remove(0), Some(1));
+        assert_eq!(treap.remove(10), None);
+        treap.set(3, 100);
+        let v: Vec<_> = (0..treap.len()).map(|i| treap.get(i).unwrap()).collect();
+        assert_eq!(v, vec![2, 10, 3, 100, 5]);
+        treap.reverse(1..4);
+        let v: Vec<_> = (0..treap.len()).map(|i| treap.get(i).unwrap()).collect();
+        assert_eq!(v, vec![2, 100, 3, 10, 5]);
+    }
+
+    #[test]
+    fn test_implicit_treap_matches_segtree() {
+        let mut rng = XorShift::default();
+        let n = 20;
+        let init: Vec<i64> = (0..n).map(|_| (rng.next() % 100) as i64).collect();
+        let mut treap = ImplicitTreap::<OpMin<i64>, ActTrivial>::from(init.clone());
+        let mut seg = SegTree::<OpMin<i64>>::from(init);
+        for _ in 0..500 {
+            let i = rng.next() as usize % n;
+            let x = (rng.next() % 100) as i64;
+            treap.set(i, x);
+            seg.set(i, x);
+            let l = rng.next() as usize % (n + 1);
+            let r = rng.next() as usize % (n + 1);
+            let (l, r) = (l.min(r), l.max(r));
+            assert_eq!(treap.fold(l..r), seg.fold(l..r));
+        }
+    }
+
+    #[test]
+    fn test_implicit_treap_random() {
+        let mut rng = XorShift::default();
+        let mut treap = ImplicitTreap::<OpMin<i64>, OpAdd<i64>>::new();
+        let mut naive: Vec<i64> = Vec::new();
+        for _ in 0..3000 {
+            let n = naive.len();
+            match rng.next() % 5 {
+                0 => {
+                    let i = rng.next() as usize % (n + 1);
+                    let x = (rng.next() % 100) as i64;
+                    treap.insert(i, x);
+                    naive.insert(i, x);
+                }
+                1 if n > 0 => {
+                    let i = rng.next() as usize % n;
+                    assert_eq!(treap.remove(i), Some(naive.remove(i)));
+                }
+                2 => {
+                    let l = rng.next() as usize % (n + 1);
+                    let r = rng.next() as usize % (n + 1);
+                    let (l, r) = (l.min(r), l.max(r));
+                    treap.reverse(l..r);
+                    naive[l..r].reverse();
+                }
+                3 => {
+                    let l = rng.next() as usize % (n + 1);
+                    let r = rng.next() as usize % (n + 1);
+                    let (l, r) = (l.min(r), l.max(r));
+                    let x = (rng.next() % 10) as i64 - 5;
+                    treap.apply(l..r, x);
+                    naive[l..r].iter_mut().for_each(|v| *v += x);
+                }
+                _ => {
+                    let l = rng.next() as usize % (n + 1);
+                    let r = rng.next() as usize % (n + 1);
+                    let (l, r) = (l.min(r), l.max(r));
+                    let min = naive[l..r].iter().copied().min().unwrap_or(std::i64::MAX);
+                    assert_eq!(treap.fold(l..r), min);
+                }
+            }
+            assert_eq!(treap.len(), naive.len());
+        }
+    }
+}
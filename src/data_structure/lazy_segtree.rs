@@ -1,19 +1,9 @@
 //! 遅延セグメント木。
 use cargo_snippet::snippet;
 
+use super::super::algebra::structure::{Monoid, MonoidAction};
 use crate::utils::integer::ceil_pow2;
 
-#[snippet("data_structure/lazy_segtree")]
-pub trait Monoid {
-    fn op(&self, other: &Self) -> Self;
-    fn e() -> Self;
-}
-
-#[snippet("data_structure/lazy_segtree")]
-pub trait Action<S: Monoid> {
-    fn act(&self, s: &S) -> S;
-}
-
 /// 遅延セグメント木
 ///
 /// S: monoid of elements
@@ -21,74 +11,76 @@ pub trait Action<S: Monoid> {
 /// *: S x X -> S right action
 #[snippet("data_structure/lazy_segtree")]
 #[derive(Debug, Clone)]
-pub struct LazySegTree<S, X> {
+pub struct LazySegTree<S: Monoid, X: MonoidAction<S>> {
     n: usize,
     size: usize,
     log: usize,
-    data: Vec<S>,
-    lazy: Vec<X>,
+    data: Vec<S::Set>,
+    lazy: Vec<X::Set>,
+    monoid: S,
+    action: X,
 }
 
 #[snippet("data_structure/lazy_segtree")]
 impl<S, X> LazySegTree<S, X>
 where
-    S: Monoid + Clone,
-    X: Monoid + Action<S> + Clone,
+    S: Monoid,
+    S::Set: Clone,
+    X: MonoidAction<S>,
+    X::Set: Clone,
 {
-    pub fn new(arr: &Vec<S>) -> Self {
-        let n = arr.len();
+    pub fn new(n: usize) -> Self {
+        let monoid = S::default();
+        let action = X::default();
         let log = ceil_pow2(n);
         let size = 1 << log;
-        let data = vec![S::e(); size << 1];
-        let lazy = vec![X::e(); size];
-        let mut lst = Self {
+        let data = vec![monoid.id(); size << 1];
+        let lazy = vec![action.id(); size];
+        Self {
             n,
             size,
             log,
             data,
             lazy,
-        };
-        for (i, val) in arr.into_iter().enumerate() {
-            lst.data[size + i] = (*val).clone();
-        }
-        for i in (1..size).rev() {
-            lst.update(i);
+            monoid,
+            action,
         }
-        lst
     }
 
     fn update(&mut self, i: usize) {
-        self.data[i] = self.data[i << 1].op(&self.data[(i << 1) + 1]);
+        self.data[i] = self
+            .monoid
+            .op(self.data[i << 1].clone(), self.data[(i << 1) + 1].clone());
     }
 
-    fn all_apply(&mut self, k: usize, x: &X) {
-        // data[k] = data[k] . x
-        self.data[k] = x.act(&self.data[k]);
+    fn all_apply(&mut self, k: usize, f: X::Set) {
+        // data[k] = data[k] . f
+        self.data[k] = self.action.act(self.data[k].clone(), f.clone());
         if k < self.size {
-            // lazy[k] = lazy[k] . x
-            self.lazy[k] = x.op(&self.lazy[k]);
+            // lazy[k] = lazy[k] . f
+            self.lazy[k] = self.action.op(self.lazy[k].clone(), f);
         }
     }
 
     fn push(&mut self, k: usize) {
         // push down lazy action at k
-        self.all_apply(k << 1, &self.lazy[k].clone());
-        self.all_apply((k << 1) + 1, &self.lazy[k].clone());
-        self.lazy[k] = X::e();
+        let f = std::mem::replace(&mut self.lazy[k], self.action.id());
+        self.all_apply(k << 1, f.clone());
+        self.all_apply((k << 1) + 1, f);
     }
 
-    pub fn set(&mut self, i: usize, val: S) {
+    pub fn set(&mut self, i: usize, val: S::Set) {
         let i = self.size + i;
         for j in (1..=self.log).rev() {
             self.push(i >> j);
         }
-        self.data[i] = val.clone();
+        self.data[i] = val;
         for j in 1..=self.log {
             self.update(i >> j);
         }
     }
 
-    pub fn get(&mut self, i: usize) -> &S {
+    pub fn get(&mut self, i: usize) -> &S::Set {
         let i = self.size + i;
         for j in (1..=self.log).rev() {
             self.push(i >> j);
@@ -96,9 +88,9 @@ where
         &self.data[i]
     }
 
-    pub fn prod(&mut self, l: usize, r: usize) -> S {
+    pub fn prod(&mut self, l: usize, r: usize) -> S::Set {
         if l == r {
-            return S::e();
+            return self.monoid.id();
         }
         let mut l = self.size + l;
         let mut r = self.size + r;
@@ -112,39 +104,39 @@ where
             }
         }
 
-        let mut sml = S::e();
-        let mut smr = S::e();
+        let mut sml = self.monoid.id();
+        let mut smr = self.monoid.id();
         while l < r {
             if l & 1 == 1 {
-                sml = sml.op(&self.data[l]);
+                sml = self.monoid.op(sml, self.data[l].clone());
                 l += 1;
             }
             if r & 1 == 1 {
                 r -= 1;
-                smr = self.data[r].op(&smr);
+                smr = self.monoid.op(self.data[r].clone(), smr);
             }
             l >>= 1;
             r >>= 1;
         }
-        sml.op(&smr)
+        self.monoid.op(sml, smr)
     }
 
-    pub fn all_prod(&self) -> S {
+    pub fn all_prod(&self) -> S::Set {
         self.data[1].clone()
     }
 
-    pub fn apply(&mut self, i: usize, x: &X) {
+    pub fn apply(&mut self, i: usize, f: X::Set) {
         let i = self.size + i;
         for j in (1..=self.log).rev() {
             self.push(i >> j);
         }
-        self.data[i] = x.act(&self.data[i]);
+        self.data[i] = self.action.act(self.data[i].clone(), f);
         for j in 1..=self.log {
             self.update(i >> j);
         }
     }
 
-    pub fn apply_range(&mut self, l: usize, r: usize, x: &X) {
+    pub fn apply_range(&mut self, l: usize, r: usize, f: X::Set) {
         if l == r {
             return;
         }
@@ -163,12 +155,12 @@ where
         let (l2, r2) = (l, r);
         while l < r {
             if l & 1 == 1 {
-                self.all_apply(l, x);
+                self.all_apply(l, f.clone());
                 l += 1;
             }
             if r & 1 == 1 {
                 r -= 1;
-                self.all_apply(r, x);
+                self.all_apply(r, f.clone());
             }
             l >>= 1;
             r >>= 1;
@@ -189,7 +181,7 @@ where
 
     pub fn max_right<F>(&mut self, l: usize, f: F) -> usize
     where
-        F: Fn(&S) -> bool,
+        F: Fn(&S::Set) -> bool,
     {
         if l == self.n {
             return self.n;
@@ -200,16 +192,16 @@ where
             self.push(l >> i);
         }
 
-        let mut sm = S::e();
+        let mut sm = self.monoid.id();
         loop {
             while l & 1 == 0 {
                 l >>= 1;
             }
-            if !(f(&sm.op(&self.data[l]))) {
+            if !(f(&self.monoid.op(sm.clone(), self.data[l].clone()))) {
                 while l < self.size {
                     self.push(l);
                     l <<= 1;
-                    let val = sm.op(&self.data[l]);
+                    let val = self.monoid.op(sm.clone(), self.data[l].clone());
                     if f(&val) {
                         sm = val;
                         l += 1;
@@ -217,7 +209,7 @@ where
                 }
                 return l - self.size;
             }
-            sm = sm.op(&self.data[l]);
+            sm = self.monoid.op(sm, self.data[l].clone());
             l += 1;
             if l & ((!l) + 1) == l {
                 break;
@@ -228,7 +220,7 @@ where
 
     pub fn min_left<F>(&mut self, r: usize, f: F) -> usize
     where
-        F: Fn(&S) -> bool,
+        F: Fn(&S::Set) -> bool,
     {
         if r == 0 {
             return 0;
@@ -239,17 +231,17 @@ where
             self.push((r - 1) >> i);
         }
 
-        let mut sm = S::e();
+        let mut sm = self.monoid.id();
         loop {
             r -= 1;
-            while r > 0 && r & 1 == 0 {
+            while r > 1 && r & 1 == 1 {
                 r >>= 1;
             }
-            if !(f(&self.data[r].op(&sm))) {
+            if !(f(&self.monoid.op(self.data[r].clone(), sm.clone()))) {
                 while r < self.size {
                     self.push(r);
                     r = (r << 1) + 1;
-                    let val = self.data[r].op(&sm);
+                    let val = self.monoid.op(self.data[r].clone(), sm.clone());
                     if f(&val) {
                         sm = val;
                         r -= 1;
@@ -257,7 +249,7 @@ where
                 }
                 return r + 1 - self.size;
             }
-            sm = self.data[r].op(&sm);
+            sm = self.monoid.op(self.data[r].clone(), sm);
             if r & ((!r) + 1) == r {
                 break;
             }
@@ -267,7 +259,33 @@ where
 }
 
 #[snippet("data_structure/lazy_segtree")]
-impl<S: std::fmt::Debug, X: std::fmt::Debug> LazySegTree<S, X> {
+impl<S, X> From<Vec<S::Set>> for LazySegTree<S, X>
+where
+    S: Monoid,
+    S::Set: Clone,
+    X: MonoidAction<S>,
+    X::Set: Clone,
+{
+    fn from(v: Vec<S::Set>) -> Self {
+        let mut lst = Self::new(v.len());
+        for (i, val) in v.into_iter().enumerate() {
+            lst.data[lst.size + i] = val;
+        }
+        for i in (1..lst.size).rev() {
+            lst.update(i);
+        }
+        lst
+    }
+}
+
+#[snippet("data_structure/lazy_segtree")]
+impl<S, X> LazySegTree<S, X>
+where
+    S: Monoid,
+    S::Set: std::fmt::Debug,
+    X: MonoidAction<S>,
+    X::Set: std::fmt::Debug,
+{
     pub fn debug(&self) {
         #[cfg(debug_assertions)]
         println!("---");
@@ -285,19 +303,373 @@ impl<S: std::fmt::Debug, X: std::fmt::Debug> LazySegTree<S, X> {
     }
 }
 
+/// デバッグやテスト用の素朴な実装。
+#[derive(Debug, Clone)]
+struct NaiveLazySegTree<S: Monoid, X: MonoidAction<S>> {
+    len: usize,
+    data: Vec<S::Set>,
+    monoid: S,
+    action: X,
+}
+
+#[allow(dead_code)]
+impl<S, X> NaiveLazySegTree<S, X>
+where
+    S: Monoid,
+    S::Set: Clone,
+    X: MonoidAction<S>,
+    X::Set: Clone,
+{
+    pub fn new(n: usize) -> Self {
+        let monoid = S::default();
+        Self {
+            len: n,
+            data: vec![monoid.id(); n],
+            monoid,
+            action: X::default(),
+        }
+    }
+
+    pub fn set(&mut self, i: usize, val: S::Set) {
+        self.data[i] = val;
+    }
+
+    pub fn get(&mut self, i: usize) -> &S::Set {
+        &self.data[i]
+    }
+
+    pub fn prod(&mut self, l: usize, r: usize) -> S::Set {
+        let mut x = self.monoid.id();
+        for i in l..r {
+            x = self.monoid.op(x, self.data[i].clone());
+        }
+        x
+    }
+
+    pub fn all_prod(&self) -> S::Set {
+        let mut x = self.monoid.id();
+        for v in &self.data {
+            x = self.monoid.op(x, v.clone());
+        }
+        x
+    }
+
+    pub fn apply(&mut self, i: usize, f: X::Set) {
+        self.data[i] = self.action.act(self.data[i].clone(), f);
+    }
+
+    pub fn apply_range(&mut self, l: usize, r: usize, f: X::Set) {
+        for i in l..r {
+            self.apply(i, f.clone());
+        }
+    }
+
+    pub fn max_right<F>(&mut self, l: usize, f: F) -> usize
+    where
+        F: Fn(&S::Set) -> bool,
+    {
+        let mut v = self.monoid.id();
+        assert!(f(&v));
+
+        let mut l = l;
+        while l < self.len {
+            v = self.monoid.op(v, self.data[l].clone());
+            if !f(&v) {
+                break;
+            }
+            l += 1;
+        }
+        l
+    }
+
+    pub fn min_left<F>(&mut self, r: usize, f: F) -> usize
+    where
+        F: Fn(&S::Set) -> bool,
+    {
+        let mut v = self.monoid.id();
+        assert!(f(&v));
+
+        let mut r = r;
+        while r > 0 {
+            r -= 1;
+            v = self.monoid.op(self.data[r].clone(), v);
+            if !f(&v) {
+                r += 1;
+                break;
+            }
+        }
+        r
+    }
+}
+
+impl<S: Monoid, X: MonoidAction<S>> From<Vec<S::Set>> for NaiveLazySegTree<S, X> {
+    fn from(v: Vec<S::Set>) -> Self {
+        Self {
+            len: v.len(),
+            data: v,
+            monoid: S::default(),
+            action: X::default(),
+        }
+    }
+}
+
+/// 以前の遅延セグメント木で使っていたトレイトと、
+/// それを `algebra::structure` のトレイトとして扱うためのアダプタ。
+pub mod compat {
+    use cargo_snippet::snippet;
+
+    use crate::algebra::structure::{Associative, Identity, Magma, MonoidAction};
+
+    #[snippet("data_structure/lazy_segtree_compat")]
+    use std::marker::PhantomData;
+
+    #[snippet("data_structure/lazy_segtree_compat")]
+    pub trait Monoid {
+        fn op(&self, other: &Self) -> Self;
+        fn e() -> Self;
+    }
+
+    #[snippet("data_structure/lazy_segtree_compat")]
+    pub trait Action<S: Monoid> {
+        fn act(&self, s: &S) -> S;
+    }
+
+    /// `Monoid` を実装した型 `T` をモノイドとして扱うためのアダプタ。
+    #[snippet("data_structure/lazy_segtree_compat")]
+    pub struct MonoidAdapter<T> {
+        phantom: PhantomData<T>,
+    }
+
+    #[snippet("data_structure/lazy_segtree_compat")]
+    impl<T> Default for MonoidAdapter<T> {
+        fn default() -> Self {
+            Self {
+                phantom: PhantomData,
+            }
+        }
+    }
+
+    #[snippet("data_structure/lazy_segtree_compat")]
+    impl<T: Monoid + Eq> Magma for MonoidAdapter<T> {
+        type Set = T;
+
+        fn op(&self, x: Self::Set, y: Self::Set) -> Self::Set {
+            x.op(&y)
+        }
+    }
+
+    #[snippet("data_structure/lazy_segtree_compat")]
+    impl<T: Monoid + Eq> Associative for MonoidAdapter<T> {}
+
+    #[snippet("data_structure/lazy_segtree_compat")]
+    impl<T: Monoid + Eq> Identity for MonoidAdapter<T> {
+        fn id(&self) -> Self::Set {
+            T::e()
+        }
+    }
+
+    /// `Monoid + Action<S>` を実装した型 `X` を、
+    /// `MonoidAdapter<S>` へのモノイド作用として扱うためのアダプタ。
+    ///
+    /// 以前のトレイトでは `f.op(&g)` は `g` を作用させた後に `f` を作用させることを表すため、
+    /// 合成の順序を入れ替えている。
+    #[snippet("data_structure/lazy_segtree_compat")]
+    pub struct ActionAdapter<X, S> {
+        phantom: PhantomData<(X, S)>,
+    }
+
+    #[snippet("data_structure/lazy_segtree_compat")]
+    impl<X, S> Default for ActionAdapter<X, S> {
+        fn default() -> Self {
+            Self {
+                phantom: PhantomData,
+            }
+        }
+    }
+
+    #[snippet("data_structure/lazy_segtree_compat")]
+    impl<X: Monoid + Eq, S> Magma for ActionAdapter<X, S> {
+        type Set = X;
+
+        fn op(&self, x: Self::Set, y: Self::Set) -> Self::Set {
+            y.op(&x)
+        }
+    }
+
+    #[snippet("data_structure/lazy_segtree_compat")]
+    impl<X: Monoid + Eq, S> Associative for ActionAdapter<X, S> {}
+
+    #[snippet("data_structure/lazy_segtree_compat")]
+    impl<X: Monoid + Eq, S> Identity for ActionAdapter<X, S> {
+        fn id(&self) -> Self::Set {
+            X::e()
+        }
+    }
+
+    #[snippet("data_structure/lazy_segtree_compat")]
+    impl<X, S> MonoidAction<MonoidAdapter<S>> for ActionAdapter<X, S>
+    where
+        X: Monoid + Action<S> + Eq,
+        S: Monoid + Eq,
+    {
+        fn act(&self, x: S, f: X) -> S {
+            f.act(&x)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::algebra::act_affine::ActAffine;
+    use crate::algebra::act_assign::ActAssign;
+    use crate::algebra::act_flip::ActFlip;
+    use crate::algebra::op_add::OpAdd;
+    use crate::algebra::op_max::OpMax;
+    use crate::algebra::op_min::OpMin;
+    use crate::algebra::op_sized_add::OpSizedAdd;
+    use crate::algebra::structure::{Associative, Identity, Magma};
+    use crate::math::mod_int::{FiniteField, Modulo};
+    use crate::utils::test_rng::XorShift;
 
     #[test]
     fn test_lazy_segtree() {
-        #[derive(Debug, Clone)]
+        #[derive(Debug, Clone, PartialEq, Eq)]
         struct S {
             a: u64,
             size: u64,
         }
 
-        #[derive(Debug, Clone)]
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        struct X {
+            a: u64,
+            b: u64,
+        }
+
+        #[derive(Default)]
+        struct OpS;
+
+        impl Magma for OpS {
+            type Set = S;
+
+            fn op(&self, x: S, y: S) -> S {
+                S {
+                    a: x.a + y.a,
+                    size: x.size + y.size,
+                }
+            }
+        }
+
+        impl Associative for OpS {}
+
+        impl Identity for OpS {
+            fn id(&self) -> S {
+                S { a: 0, size: 0 }
+            }
+        }
+
+        #[derive(Default)]
+        struct OpX;
+
+        impl Magma for OpX {
+            type Set = X;
+
+            fn op(&self, f: X, g: X) -> X {
+                X {
+                    a: f.a * g.a,
+                    b: f.b * g.a + g.b,
+                }
+            }
+        }
+
+        impl Associative for OpX {}
+
+        impl Identity for OpX {
+            fn id(&self) -> X {
+                X { a: 1, b: 0 }
+            }
+        }
+
+        impl MonoidAction<OpS> for OpX {
+            fn act(&self, x: S, f: X) -> S {
+                S {
+                    a: x.a * f.a + x.size * f.b,
+                    size: x.size,
+                }
+            }
+        }
+
+        let a = vec![1, 2, 3, 4, 5];
+
+        let mut vs = Vec::new();
+        for y in a {
+            vs.push(S { a: y, size: 1 });
+        }
+
+        let mut seg = LazySegTree::<OpS, OpX>::from(vs);
+        assert_eq!(seg.prod(0, 5).a, 15);
+        seg.apply_range(2, 4, X { a: 100, b: 101 });
+        assert_eq!(seg.prod(0, 3).a, 404);
+        seg.apply_range(1, 3, X { a: 102, b: 103 });
+        assert_eq!(seg.prod(2, 5).a, 41511);
+        seg.apply_range(2, 5, X { a: 104, b: 105 });
+        assert_eq!(seg.prod(0, 5).a, 4317767);
+    }
+
+    #[test]
+    fn test_lazy_segtree_op_min() {
+        // 区間加算・区間最小値
+        #[derive(Default)]
+        struct ActAdd;
+
+        impl Magma for ActAdd {
+            type Set = i64;
+
+            fn op(&self, f: i64, g: i64) -> i64 {
+                f + g
+            }
+        }
+
+        impl Associative for ActAdd {}
+
+        impl Identity for ActAdd {
+            fn id(&self) -> i64 {
+                0
+            }
+        }
+
+        impl MonoidAction<OpMin<i64>> for ActAdd {
+            fn act(&self, x: i64, f: i64) -> i64 {
+                if x == std::i64::MAX {
+                    x
+                } else {
+                    x + f
+                }
+            }
+        }
+
+        let mut seg = LazySegTree::<OpMin<i64>, ActAdd>::from(vec![3, 1, 4, 1, 5]);
+        assert_eq!(seg.prod(0, 5), 1);
+        seg.apply_range(0, 2, 10);
+        assert_eq!(seg.prod(0, 3), 4);
+        seg.apply(3, -5);
+        assert_eq!(seg.all_prod(), -4);
+        assert_eq!(*seg.get(1), 11);
+        assert_eq!(seg.max_right(0, |&x| x > 0), 3);
+        assert_eq!(seg.min_left(5, |&x| x > 0), 4);
+    }
+
+    #[test]
+    fn test_lazy_segtree_compat() {
+        use super::compat::{Action, ActionAdapter, Monoid, MonoidAdapter};
+
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        struct S {
+            a: u64,
+            size: u64,
+        }
+
+        #[derive(Debug, Clone, PartialEq, Eq)]
         struct X {
             a: u64,
             b: u64,
@@ -343,13 +715,152 @@ mod tests {
             vs.push(S { a: y, size: 1 });
         }
 
-        let mut seg = LazySegTree::<S, X>::new(&vs);
+        let mut seg = LazySegTree::<MonoidAdapter<S>, ActionAdapter<X, S>>::from(vs);
         assert_eq!(seg.prod(0, 5).a, 15);
-        seg.apply_range(2, 4, &X { a: 100, b: 101 });
+        seg.apply_range(2, 4, X { a: 100, b: 101 });
         assert_eq!(seg.prod(0, 3).a, 404);
-        seg.apply_range(1, 3, &X { a: 102, b: 103 });
+        seg.apply_range(1, 3, X { a: 102, b: 103 });
         assert_eq!(seg.prod(2, 5).a, 41511);
-        seg.apply_range(2, 5, &X { a: 104, b: 105 });
+        seg.apply_range(2, 5, X { a: 104, b: 105 });
         assert_eq!(seg.prod(0, 5).a, 4317767);
     }
+
+    fn random_range(rng: &mut XorShift, n: usize) -> (usize, usize) {
+        let l = rng.next() as usize % (n + 1);
+        let r = rng.next() as usize % (n + 1);
+        (l.min(r), l.max(r))
+    }
+
+    /// ランダムな操作を行い、素朴な実装と結果が一致することを確かめる。
+    fn test_with_naive<S, X, F, G>(n: usize, gen_val: F, gen_act: G)
+    where
+        S: Monoid,
+        S::Set: Clone + std::fmt::Debug,
+        X: MonoidAction<S>,
+        X::Set: Clone,
+        F: Fn(&mut XorShift) -> S::Set,
+        G: Fn(&mut XorShift) -> X::Set,
+    {
+        let mut rng = XorShift::default();
+        let v: Vec<_> = (0..n).map(|_| gen_val(&mut rng)).collect();
+        let mut seg = LazySegTree::<S, X>::from(v.clone());
+        let mut naive = NaiveLazySegTree::<S, X>::from(v);
+        for _ in 0..500 {
+            let i = rng.next() as usize % n;
+            let (l, r) = random_range(&mut rng, n);
+            match rng.next() % 4 {
+                0 => {
+                    let f = gen_act(&mut rng);
+                    seg.apply_range(l, r, f.clone());
+                    naive.apply_range(l, r, f);
+                }
+                1 => {
+                    let f = gen_act(&mut rng);
+                    seg.apply(i, f.clone());
+                    naive.apply(i, f);
+                }
+                2 => {
+                    let x = gen_val(&mut rng);
+                    seg.set(i, x.clone());
+                    naive.set(i, x);
+                }
+                _ => {}
+            }
+            let (l, r) = random_range(&mut rng, n);
+            assert_eq!(seg.prod(l, r), naive.prod(l, r));
+            assert_eq!(seg.get(i), naive.get(i));
+            assert_eq!(seg.all_prod(), naive.all_prod());
+        }
+    }
+
+    #[test]
+    fn test_lazy_segtree_range_add_range_sum() {
+        test_with_naive::<OpSizedAdd<i64>, OpAdd<i64>, _, _>(
+            20,
+            |rng| ((rng.next() % 200) as i64 - 100, 1),
+            |rng| (rng.next() % 200) as i64 - 100,
+        );
+    }
+
+    #[test]
+    fn test_lazy_segtree_range_assign_range_min() {
+        test_with_naive::<OpMin<i64>, ActAssign<i64>, _, _>(
+            20,
+            |rng| (rng.next() % 200) as i64 - 100,
+            |rng| Some((rng.next() % 200) as i64 - 100),
+        );
+    }
+
+    #[test]
+    fn test_lazy_segtree_range_assign_range_max() {
+        test_with_naive::<OpMax<i64>, ActAssign<i64>, _, _>(
+            20,
+            |rng| (rng.next() % 200) as i64 - 100,
+            |rng| Some((rng.next() % 200) as i64 - 100),
+        );
+    }
+
+    #[test]
+    fn test_lazy_segtree_range_add_range_min() {
+        test_with_naive::<OpMin<i64>, OpAdd<i64>, _, _>(
+            20,
+            |rng| (rng.next() % 200) as i64 - 100,
+            |rng| (rng.next() % 200) as i64 - 100,
+        );
+    }
+
+    #[test]
+    fn test_lazy_segtree_range_affine_range_sum() {
+        #[derive(Copy, Clone, Eq, PartialEq)]
+        struct Mod998244353;
+        impl Modulo for Mod998244353 {
+            fn modulo() -> i64 {
+                998244353
+            }
+        }
+        type F = FiniteField<Mod998244353>;
+
+        test_with_naive::<OpSizedAdd<F>, ActAffine<F>, _, _>(
+            20,
+            |rng| (F::new(rng.next() as i64 % 998244353), 1),
+            |rng| {
+                (
+                    F::new(rng.next() as i64 % 998244353),
+                    F::new(rng.next() as i64 % 998244353),
+                )
+            },
+        );
+    }
+
+    #[test]
+    fn test_lazy_segtree_range_flip_count_ones() {
+        test_with_naive::<OpSizedAdd<usize>, ActFlip, _, _>(
+            20,
+            |rng| ((rng.next() % 2) as usize, 1),
+            |rng| rng.next() % 2 == 0,
+        );
+    }
+
+    #[test]
+    fn test_lazy_segtree_bisect() {
+        let mut rng = XorShift::default();
+        let n = 20;
+        let v: Vec<_> = (0..n).map(|_| (rng.next() % 100) as i64).collect();
+        let mut seg = LazySegTree::<OpMin<i64>, OpAdd<i64>>::from(v.clone());
+        let mut naive = NaiveLazySegTree::<OpMin<i64>, OpAdd<i64>>::from(v);
+        for _ in 0..200 {
+            let (l, r) = random_range(&mut rng, n);
+            let f = (rng.next() % 21) as i64 - 10;
+            seg.apply_range(l, r, f);
+            naive.apply_range(l, r, f);
+            let t = (rng.next() % 100) as i64;
+            for i in 0..=n {
+                assert_eq!(
+                    seg.max_right(i, |&x| x >= t),
+                    naive.max_right(i, |&x| x >= t)
+                );
+                assert_eq!(seg.min_left(i, |&x| x >= t), naive.min_left(i, |&x| x >= t));
+            }
+        }
+    }
 }
@@ -0,0 +1,188 @@
+//! Leftist Heap。
+use cargo_snippet::snippet;
+
+#[snippet("data_structure/leftist_heap")]
+use std::rc::Rc;
+
+#[snippet("data_structure/leftist_heap")]
+#[derive(Debug, Clone)]
+struct LeftistNode<T> {
+    val: T,
+    // 右の子を辿って葉に至るまでの距離
+    rank: usize,
+    size: usize,
+    left: Option<Rc<LeftistNode<T>>>,
+    right: Option<Rc<LeftistNode<T>>>,
+}
+
+/// Leftist Heap
+///
+/// 併合可能な最小ヒープ。
+/// - 要素の追加
+/// - 最小の要素の取り出し
+/// - ヒープ同士の併合
+///
+/// を `O(log N)` で行うことができる。
+///
+/// ノードを `Rc` で共有しているため、`clone` は `O(1)` で行え、
+/// 複製したヒープを操作しても元のヒープは変化しない (永続)。
+/// 共有されていないノードは書き換えて再利用するため、永続性を使わない場合も余計な複製は行わない。
+#[snippet("data_structure/leftist_heap")]
+#[derive(Debug, Clone)]
+pub struct LeftistHeap<T> {
+    root: Option<Rc<LeftistNode<T>>>,
+}
+
+#[snippet("data_structure/leftist_heap")]
+impl<T: Ord + Clone> Default for LeftistHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[snippet("data_structure/leftist_heap")]
+impl<T: Ord + Clone> LeftistHeap<T> {
+    /// 空のヒープを作成する。
+    pub fn new() -> Self {
+        Self { root: None }
+    }
+
+    /// 要素数を返す。
+    pub fn len(&self) -> usize {
+        self.root.as_ref().map_or(0, |t| t.size)
+    }
+
+    /// 空かどうかを返す。
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    fn rank(t: &Option<Rc<LeftistNode<T>>>) -> usize {
+        t.as_ref().map_or(0, |t| t.rank)
+    }
+
+    fn size(t: &Option<Rc<LeftistNode<T>>>) -> usize {
+        t.as_ref().map_or(0, |t| t.size)
+    }
+
+    fn merge(
+        a: Option<Rc<LeftistNode<T>>>,
+        b: Option<Rc<LeftistNode<T>>>,
+    ) -> Option<Rc<LeftistNode<T>>> {
+        let (mut a, b) = match (a, b) {
+            (None, t) | (t, None) => return t,
+            (Some(a), Some(b)) => {
+                if a.val <= b.val {
+                    (a, b)
+                } else {
+                    (b, a)
+                }
+            }
+        };
+        let node = Rc::make_mut(&mut a);
+        node.right = Self::merge(node.right.take(), Some(b));
+        if Self::rank(&node.left) < Self::rank(&node.right) {
+            std::mem::swap(&mut node.left, &mut node.right);
+        }
+        node.rank = Self::rank(&node.right) + 1;
+        node.size = Self::size(&node.left) + Self::size(&node.right) + 1;
+        Some(a)
+    }
+
+    /// 要素 `val` を追加する。
+    pub fn push(&mut self, val: T) {
+        let node = Rc::new(LeftistNode {
+            val,
+            rank: 1,
+            size: 1,
+            left: None,
+            right: None,
+        });
+        self.root = Self::merge(self.root.take(), Some(node));
+    }
+
+    /// 最小の要素への参照を返す。
+    pub fn peek(&self) -> Option<&T> {
+        self.root.as_ref().map(|t| &t.val)
+    }
+
+    /// 最小の要素を取り出す。
+    pub fn pop(&mut self) -> Option<T> {
+        let root = self.root.take()?;
+        let (val, left, right) = match Rc::try_unwrap(root) {
+            Ok(node) => (node.val, node.left, node.right),
+            Err(rc) => (rc.val.clone(), rc.left.clone(), rc.right.clone()),
+        };
+        self.root = Self::merge(left, right);
+        Some(val)
+    }
+
+    /// ヒープ `other` の要素を全て追加する。
+    pub fn meld(&mut self, other: Self) {
+        self.root = Self::merge(self.root.take(), other.root);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_rng::XorShift;
+    use std::cmp::Reverse;
+    use std::collections::BinaryHeap;
+
+    fn drain(mut heap: LeftistHeap<u64>) -> Vec<u64> {
+        let mut res = Vec::new();
+        while let Some(x) = heap.pop() {
+            res.push(x);
+        }
+        res
+    }
+
+    #[test]
+    fn test_leftist_heap_meld() {
+        let mut rng = XorShift::default();
+        let mut heaps: Vec<LeftistHeap<u64>> = (0..10).map(|_| LeftistHeap::new()).collect();
+        let mut naive: Vec<BinaryHeap<Reverse<u64>>> = vec![BinaryHeap::new(); 10];
+        for _ in 0..3000 {
+            let i = rng.next() as usize % 10;
+            match rng.next() % 4 {
+                0 | 1 => {
+                    let x = rng.next() % 100;
+                    heaps[i].push(x);
+                    naive[i].push(Reverse(x));
+                }
+                2 => {
+                    assert_eq!(heaps[i].pop(), naive[i].pop().map(|Reverse(x)| x));
+                }
+                _ => {
+                    let j = rng.next() as usize % 10;
+                    if i != j {
+                        let h = std::mem::take(&mut heaps[j]);
+                        heaps[i].meld(h);
+                        let h = std::mem::take(&mut naive[j]);
+                        naive[i].extend(h);
+                    }
+                }
+            }
+            assert_eq!(heaps[i].len(), naive[i].len());
+            assert_eq!(heaps[i].peek(), naive[i].peek().map(|Reverse(x)| x));
+        }
+    }
+
+    #[test]
+    fn test_leftist_heap_persistent() {
+        let mut a = LeftistHeap::new();
+        for &x in &[5, 3, 8, 1] {
+            a.push(x);
+        }
+        let mut b = a.clone();
+        b.pop();
+        b.push(4);
+        let mut c = a.clone();
+        c.meld(b.clone());
+        a.push(2);
+        assert_eq!(drain(a), vec![1, 2, 3, 5, 8]);
+        assert_eq!(drain(b), vec![3, 4, 5, 8]);
+        assert_eq!(drain(c), vec![1, 3, 3, 4, 5, 5, 8, 8]);
+    }
+}
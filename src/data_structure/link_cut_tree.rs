@@ -0,0 +1,486 @@
+//! Link-Cut Tree。
+use cargo_snippet::snippet;
+
+#[snippet("data_structure/link_cut_tree")]
+use std::mem::swap;
+
+use super::super::algebra::structure::{Monoid, MonoidAction};
+
+#[snippet("data_structure/link_cut_tree")]
+const NIL: usize = std::usize::MAX;
+
+/// Link-Cut Tree
+///
+/// 各頂点にモノイド `M` の値を持つ根付き森に対して、
+/// - 辺の追加・削除
+/// - 連結性の判定・最小共通祖先の取得
+/// - パス上の値の積の取得・パス上の値への作用
+///
+/// を償却 `O(log N)` で行うことができる。
+/// パスの両方向の積を保持しているため、`M` は非可換なモノイドでもよい。
+#[snippet("data_structure/link_cut_tree")]
+pub struct LinkCutTree<M: Monoid, X: MonoidAction<M>> {
+    left: Vec<usize>,
+    right: Vec<usize>,
+    par: Vec<usize>,
+    val: Vec<M::Set>,
+    sum: Vec<M::Set>,
+    sum_rev: Vec<M::Set>,
+    lazy: Vec<X::Set>,
+    rev: Vec<bool>,
+    monoid: M,
+    action: X,
+}
+
+#[snippet("data_structure/link_cut_tree")]
+impl<M, X> LinkCutTree<M, X>
+where
+    M: Monoid,
+    M::Set: Clone,
+    X: MonoidAction<M>,
+    X::Set: Clone,
+{
+    /// 全ての頂点の値が単位元である、`n` 頂点の辺のない森を作成する。
+    pub fn new(n: usize) -> Self {
+        let monoid = M::default();
+        Self::from(vec![monoid.id(); n])
+    }
+
+    /// 頂点 `x` が splay 木の根かを返す。
+    fn is_root(&self, x: usize) -> bool {
+        let p = self.par[x];
+        p == NIL || (self.left[p] != x && self.right[p] != x)
+    }
+
+    fn sum(&self, x: usize) -> M::Set {
+        if x == NIL {
+            self.monoid.id()
+        } else {
+            self.sum[x].clone()
+        }
+    }
+
+    fn sum_rev(&self, x: usize) -> M::Set {
+        if x == NIL {
+            self.monoid.id()
+        } else {
+            self.sum_rev[x].clone()
+        }
+    }
+
+    fn update(&mut self, x: usize) {
+        let (l, r) = (self.left[x], self.right[x]);
+        self.sum[x] = self.monoid.op(
+            self.monoid.op(self.sum(l), self.val[x].clone()),
+            self.sum(r),
+        );
+        self.sum_rev[x] = self.monoid.op(
+            self.monoid.op(self.sum_rev(r), self.val[x].clone()),
+            self.sum_rev(l),
+        );
+    }
+
+    fn apply_act(&mut self, x: usize, f: X::Set) {
+        if x == NIL {
+            return;
+        }
+        self.val[x] = self.action.act(self.val[x].clone(), f.clone());
+        self.sum[x] = self.action.act(self.sum[x].clone(), f.clone());
+        self.sum_rev[x] = self.action.act(self.sum_rev[x].clone(), f.clone());
+        self.lazy[x] = self.action.op(self.lazy[x].clone(), f);
+    }
+
+    fn apply_rev(&mut self, x: usize) {
+        if x == NIL {
+            return;
+        }
+        swap(&mut self.left[x], &mut self.right[x]);
+        swap(&mut self.sum[x], &mut self.sum_rev[x]);
+        self.rev[x] ^= true;
+    }
+
+    fn push(&mut self, x: usize) {
+        let (l, r) = (self.left[x], self.right[x]);
+        if self.rev[x] {
+            self.apply_rev(l);
+            self.apply_rev(r);
+            self.rev[x] = false;
+        }
+        let f = std::mem::replace(&mut self.lazy[x], self.action.id());
+        self.apply_act(l, f.clone());
+        self.apply_act(r, f);
+    }
+
+    fn rotate(&mut self, x: usize) {
+        let p = self.par[x];
+        let g = self.par[p];
+        if self.left[p] == x {
+            let c = self.right[x];
+            self.left[p] = c;
+            if c != NIL {
+                self.par[c] = p;
+            }
+            self.right[x] = p;
+        } else {
+            let c = self.left[x];
+            self.right[p] = c;
+            if c != NIL {
+                self.par[c] = p;
+            }
+            self.left[x] = p;
+        }
+        self.par[p] = x;
+        self.par[x] = g;
+        if g != NIL {
+            if self.left[g] == p {
+                self.left[g] = x;
+            } else if self.right[g] == p {
+                self.right[g] = x;
+            }
+        }
+        self.update(p);
+        self.update(x);
+    }
+
+    fn splay(&mut self, x: usize) {
+        let mut path = vec![x];
+        let mut y = x;
+        while !self.is_root(y) {
+            y = self.par[y];
+            path.push(y);
+        }
+        while let Some(y) = path.pop() {
+            self.push(y);
+        }
+        while !self.is_root(x) {
+            let p = self.par[x];
+            if !self.is_root(p) {
+                let g = self.par[p];
+                if (self.left[g] == p) == (self.left[p] == x) {
+                    self.rotate(p);
+                } else {
+                    self.rotate(x);
+                }
+            }
+            self.rotate(x);
+        }
+    }
+
+    /// 根から頂点 `x` までのパスを一つの splay 木にまとめ、最後に繋ぎ替えた頂点を返す。
+    fn access(&mut self, x: usize) -> usize {
+        let mut last = NIL;
+        let mut y = x;
+        while y != NIL {
+            self.splay(y);
+            self.right[y] = last;
+            self.update(y);
+            last = y;
+            y = self.par[y];
+        }
+        self.splay(x);
+        last
+    }
+
+    /// 頂点 `x` を森の根にする。
+    pub fn evert(&mut self, x: usize) {
+        self.access(x);
+        self.apply_rev(x);
+    }
+
+    /// 頂点 `x` の属する木の根を返す。
+    pub fn root(&mut self, x: usize) -> usize {
+        self.access(x);
+        let mut y = x;
+        loop {
+            self.push(y);
+            if self.left[y] == NIL {
+                break;
+            }
+            y = self.left[y];
+        }
+        self.splay(y);
+        y
+    }
+
+    /// 頂点 `u` と 頂点 `v` が同じ木に属しているかを返す。
+    pub fn connected(&mut self, u: usize, v: usize) -> bool {
+        self.root(u) == self.root(v)
+    }
+
+    /// 頂点 `c` を根にした上で、頂点 `p` の子として繋ぐ。
+    pub fn link(&mut self, c: usize, p: usize) {
+        assert!(!self.connected(c, p), "already connected");
+        self.evert(c);
+        self.par[c] = p;
+    }
+
+    /// 現在の根に関する頂点 `x` の親を返す。`x` が根の場合は `None` を返す。
+    fn parent(&mut self, x: usize) -> Option<usize> {
+        self.access(x);
+        self.push(x);
+        let mut y = self.left[x];
+        if y == NIL {
+            return None;
+        }
+        loop {
+            self.push(y);
+            if self.right[y] == NIL {
+                break;
+            }
+            y = self.right[y];
+        }
+        self.splay(y);
+        Some(y)
+    }
+
+    /// 頂点 `x` と その親を繋ぐ辺を削除する。
+    fn cut_parent(&mut self, x: usize) {
+        self.access(x);
+        let l = self.left[x];
+        assert!(l != NIL, "no parent");
+        self.par[l] = NIL;
+        self.left[x] = NIL;
+        self.update(x);
+    }
+
+    /// 辺 `(u, v)` を削除する。各木の根は変わらない。
+    pub fn cut(&mut self, u: usize, v: usize) {
+        if self.parent(u) == Some(v) {
+            self.cut_parent(u);
+        } else if self.parent(v) == Some(u) {
+            self.cut_parent(v);
+        } else {
+            panic!("no such edge");
+        }
+    }
+
+    /// 現在の根に関する頂点 `u` と 頂点 `v` の最小共通祖先を返す。連結でない場合は `None` を返す。
+    pub fn lca(&mut self, u: usize, v: usize) -> Option<usize> {
+        if !self.connected(u, v) {
+            return None;
+        }
+        self.access(u);
+        Some(self.access(v))
+    }
+
+    /// 頂点 `u` から頂点 `v` へのパス上の値を順に掛けた積を返す。
+    /// 頂点 `u` が根になる。
+    pub fn fold(&mut self, u: usize, v: usize) -> M::Set {
+        assert!(self.connected(u, v), "not connected");
+        self.evert(u);
+        self.access(v);
+        self.sum[v].clone()
+    }
+
+    /// 頂点 `u` から頂点 `v` へのパス上の値に `f` を作用させる。
+    /// 頂点 `u` が根になる。
+    pub fn apply(&mut self, u: usize, v: usize, f: X::Set) {
+        assert!(self.connected(u, v), "not connected");
+        self.evert(u);
+        self.access(v);
+        self.apply_act(v, f);
+    }
+
+    /// 頂点 `x` の値を返す。
+    pub fn get(&mut self, x: usize) -> M::Set {
+        self.access(x);
+        self.val[x].clone()
+    }
+
+    /// 頂点 `x` の値を `val` に変更する。
+    pub fn set(&mut self, x: usize, val: M::Set) {
+        self.access(x);
+        self.val[x] = val;
+        self.update(x);
+    }
+}
+
+#[snippet("data_structure/link_cut_tree")]
+impl<M, X> From<Vec<M::Set>> for LinkCutTree<M, X>
+where
+    M: Monoid,
+    M::Set: Clone,
+    X: MonoidAction<M>,
+    X::Set: Clone,
+{
+    fn from(v: Vec<M::Set>) -> Self {
+        let n = v.len();
+        let action = X::default();
+        Self {
+            left: vec![NIL; n],
+            right: vec![NIL; n],
+            par: vec![NIL; n],
+            sum: v.clone(),
+            sum_rev: v.clone(),
+            val: v,
+            lazy: vec![action.id(); n],
+            rev: vec![false; n],
+            monoid: M::default(),
+            action,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algebra::act_trivial::ActTrivial;
+    use crate::algebra::op_add::OpAdd;
+    use crate::algebra::op_sized_add::OpSizedAdd;
+    use crate::algebra::structure::{Associative, Identity, Magma};
+    use crate::utils::test_rng::XorShift;
+
+    /// 親の配列で森を管理する愚直な実装
+    struct NaiveForest {
+        par: Vec<usize>,
+    }
+
+    impl NaiveForest {
+        fn root_path(&self, x: usize) -> Vec<usize> {
+            let mut path = vec![x];
+            while self.par[*path.last().unwrap()] != NIL {
+                path.push(self.par[*path.last().unwrap()]);
+            }
+            path
+        }
+
+        fn evert(&mut self, x: usize) {
+            let path = self.root_path(x);
+            for w in path.windows(2).rev() {
+                self.par[w[1]] = w[0];
+            }
+            self.par[x] = NIL;
+        }
+
+        fn lca(&self, u: usize, v: usize) -> Option<usize> {
+            let pu = self.root_path(u);
+            let pv = self.root_path(v);
+            pu.into_iter().find(|x| pv.contains(x))
+        }
+
+        /// 頂点 `u` から頂点 `v` へのパス
+        fn path(&self, u: usize, v: usize) -> Vec<usize> {
+            let w = self.lca(u, v).unwrap();
+            let pu = self.root_path(u);
+            let pv = self.root_path(v);
+            let mut path: Vec<_> = pu.into_iter().take_while(|&x| x != w).collect();
+            path.push(w);
+            let down: Vec<_> = pv.into_iter().take_while(|&x| x != w).collect();
+            path.extend(down.into_iter().rev());
+            path
+        }
+    }
+
+    #[test]
+    fn test_link_cut_tree_non_commutative() {
+        // 非可換な演算 (アフィン変換の合成)
+        #[derive(Default)]
+        struct OpCompose;
+
+        impl Magma for OpCompose {
+            type Set = (i64, i64);
+
+            // x を適用した後に y を適用する
+            fn op(&self, x: (i64, i64), y: (i64, i64)) -> (i64, i64) {
+                (x.0 * y.0 % 998244353, (x.1 * y.0 + y.1) % 998244353)
+            }
+        }
+
+        impl Associative for OpCompose {}
+
+        impl Identity for OpCompose {
+            fn id(&self) -> (i64, i64) {
+                (1, 0)
+            }
+        }
+
+        let op = OpCompose;
+        let mut rng = XorShift::default();
+        let n = 15;
+        let mut val: Vec<_> = (0..n)
+            .map(|_| ((rng.next() % 10 + 1) as i64, (rng.next() % 10) as i64))
+            .collect();
+        let mut lct = LinkCutTree::<OpCompose, ActTrivial>::from(val.clone());
+        let mut naive = NaiveForest { par: vec![NIL; n] };
+        for _ in 0..3000 {
+            let u = rng.next() as usize % n;
+            let v = rng.next() as usize % n;
+            let connected = naive.lca(u, v).is_some();
+            assert_eq!(lct.connected(u, v), connected);
+            match rng.next() % 5 {
+                0 if !connected => {
+                    lct.link(u, v);
+                    naive.evert(u);
+                    naive.par[u] = v;
+                }
+                1 if naive.par[u] != NIL => {
+                    let p = naive.par[u];
+                    if rng.next() % 2 == 0 {
+                        lct.cut(u, p);
+                    } else {
+                        lct.cut(p, u);
+                    }
+                    naive.par[u] = NIL;
+                }
+                2 => {
+                    assert_eq!(lct.lca(u, v), naive.lca(u, v));
+                }
+                3 => {
+                    let x = ((rng.next() % 10 + 1) as i64, (rng.next() % 10) as i64);
+                    lct.set(u, x);
+                    val[u] = x;
+                }
+                _ if connected => {
+                    let expected = naive
+                        .path(u, v)
+                        .into_iter()
+                        .fold(op.id(), |s, x| op.op(s, val[x]));
+                    assert_eq!(lct.fold(u, v), expected);
+                    naive.evert(u);
+                }
+                _ => {}
+            }
+            assert_eq!(lct.root(u), *naive.root_path(u).last().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_link_cut_tree_path_add() {
+        let mut rng = XorShift::default();
+        let n = 15;
+        let mut val = vec![0i64; n];
+        let mut lct = LinkCutTree::<OpSizedAdd<i64>, OpAdd<i64>>::from(vec![(0, 1); n]);
+        let mut naive = NaiveForest { par: vec![NIL; n] };
+        for i in 1..n {
+            let p = rng.next() as usize % i;
+            lct.link(i, p);
+            naive.evert(i);
+            naive.par[i] = p;
+        }
+        for _ in 0..1000 {
+            let u = rng.next() as usize % n;
+            let v = rng.next() as usize % n;
+            let path = naive.path(u, v);
+            if rng.next() % 2 == 0 {
+                let x = (rng.next() % 10) as i64;
+                lct.apply(u, v, x);
+                path.iter().for_each(|&i| val[i] += x);
+            } else {
+                let s = path.iter().map(|&i| val[i]).sum::<i64>();
+                assert_eq!(lct.fold(u, v), (s, path.len()));
+            }
+            naive.evert(u);
+            let w = rng.next() as usize % n;
+            assert_eq!(lct.get(w), (val[w], 1));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "no such edge")]
+    fn test_link_cut_tree_cut_non_adjacent() {
+        let mut lct = LinkCutTree::<OpAdd<i64>, ActTrivial>::from(vec![0; 3]);
+        lct.link(1, 0);
+        lct.link(2, 1);
+        lct.cut(2, 0);
+    }
+}
@@ -0,0 +1,111 @@
+//! 部分永続 Union-Find木。
+use cargo_snippet::snippet;
+
+#[snippet("data_structure/partially_persistent_union_find")]
+use std::cmp::Ordering::{Greater, Less};
+
+/// 部分永続 Union-Find木。
+///
+/// `t` 回目の `union` を時刻 `t` に行ったものとして、
+/// 過去の任意の時刻における連結性を `O(log N)` で答えることができる。
+#[snippet("data_structure/partially_persistent_union_find")]
+#[derive(Debug, Clone)]
+pub struct PartiallyPersistentUnionFind {
+    now: usize,
+    par: Vec<usize>,
+    // 親に繋がれた時刻 (代表元の場合は std::usize::MAX)
+    time: Vec<usize>,
+    // 代表元の連結成分のサイズの履歴 (時刻, サイズ)
+    size: Vec<Vec<(usize, usize)>>,
+}
+
+#[snippet("data_structure/partially_persistent_union_find")]
+impl PartiallyPersistentUnionFind {
+    /// 大きさ `n` の Union-Find 木を初期化する。
+    pub fn new(n: usize) -> Self {
+        Self {
+            now: 0,
+            par: (0..n).collect(),
+            time: vec![std::usize::MAX; n],
+            size: vec![vec![(0, 1)]; n],
+        }
+    }
+
+    /// 現在の時刻 (これまでに呼ばれた `union` の回数) を返す。
+    pub fn now(&self) -> usize {
+        self.now
+    }
+
+    /// 時刻 `t` において頂点 `a` の属する連結成分の代表元を返す。
+    pub fn find_root(&self, a: usize, t: usize) -> usize {
+        let mut a = a;
+        while self.time[a] <= t {
+            a = self.par[a];
+        }
+        a
+    }
+
+    /// 辺 `(a, b)` を追加して時刻を一つ進め、追加後の連結成分の代表元を返す。
+    pub fn union(&mut self, a: usize, b: usize) -> usize {
+        self.now += 1;
+        let mut x = self.find_root(a, self.now);
+        let mut y = self.find_root(b, self.now);
+        if x == y {
+            return x;
+        }
+        let sx = self.size[x].last().unwrap().1;
+        let sy = self.size[y].last().unwrap().1;
+        if sx < sy {
+            std::mem::swap(&mut x, &mut y);
+        }
+        self.par[y] = x;
+        self.time[y] = self.now;
+        self.size[x].push((self.now, sx + sy));
+        x
+    }
+
+    /// 時刻 `t` において頂点 `a` と 頂点 `b` が同じ連結成分に属しているかを返す。
+    pub fn in_same_set(&self, a: usize, b: usize, t: usize) -> bool {
+        self.find_root(a, t) == self.find_root(b, t)
+    }
+
+    /// 時刻 `t` において頂点 `a` の属する連結成分のサイズを返す。
+    pub fn group_size(&self, a: usize, t: usize) -> usize {
+        let x = self.find_root(a, t);
+        let i = self.size[x]
+            .binary_search_by(|&(s, _)| if s <= t { Less } else { Greater })
+            .unwrap_or_else(|i| i);
+        self.size[x][i - 1].1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partially_persistent_union_find() {
+        let mut uf = PartiallyPersistentUnionFind::new(5);
+        assert_eq!(uf.now(), 0);
+        uf.union(0, 1);
+        uf.union(2, 3);
+        uf.union(1, 2);
+        uf.union(0, 3);
+        uf.union(3, 4);
+        assert_eq!(uf.now(), 5);
+
+        assert!(!uf.in_same_set(0, 1, 0));
+        assert!(uf.in_same_set(0, 1, 1));
+        assert!(!uf.in_same_set(0, 3, 2));
+        assert!(uf.in_same_set(0, 3, 3));
+        assert!(!uf.in_same_set(0, 4, 4));
+        assert!(uf.in_same_set(0, 4, 5));
+
+        assert_eq!(uf.group_size(3, 0), 1);
+        assert_eq!(uf.group_size(3, 2), 2);
+        assert_eq!(uf.group_size(3, 3), 4);
+        assert_eq!(uf.group_size(3, 4), 4);
+        assert_eq!(uf.group_size(4, 4), 1);
+        assert_eq!(uf.group_size(4, 5), 5);
+    }
+}
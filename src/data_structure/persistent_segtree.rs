@@ -0,0 +1,256 @@
+//! 永続セグメント木。
+use cargo_snippet::snippet;
+
+use super::super::algebra::structure::Monoid;
+
+#[snippet("data_structure/persistent_segtree")]
+use std::ops::Range;
+
+#[snippet("data_structure/persistent_segtree")]
+#[derive(Debug, Clone)]
+struct PersistentSegTreeNode<T> {
+    val: T,
+    left: usize,
+    right: usize,
+}
+
+/// 永続セグメント木のバージョン。
+#[snippet("data_structure/persistent_segtree")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Version(usize);
+
+/// 永続セグメント木。
+///
+/// モノイドに対して、
+/// - 要素の一点更新 (新しいバージョンを返す)
+/// - 任意のバージョンにおける区間の要素の積の取得
+///
+/// を `O(log N)` で行うことができる。
+/// 更新ではパス上の `O(log N)` 個のノードのみを新たに作成し、残りのノードは共有する。
+#[snippet("data_structure/persistent_segtree")]
+pub struct PersistentSegTree<M: Monoid> {
+    len: usize,
+    nodes: Vec<PersistentSegTreeNode<M::Set>>,
+    init: Version,
+    monoid: M,
+}
+
+#[snippet("data_structure/persistent_segtree")]
+impl<M> PersistentSegTree<M>
+where
+    M: Monoid,
+    M::Set: Clone + Copy,
+{
+    /// 全ての要素が単位元である長さ `n` の永続セグメント木を作成する。
+    pub fn new(n: usize) -> Self {
+        let monoid = M::default();
+        let v = vec![monoid.id(); n];
+        Self::from(v)
+    }
+
+    /// 初期状態のバージョンを返す。
+    pub fn init(&self) -> Version {
+        self.init
+    }
+
+    fn build(&mut self, lo: usize, hi: usize, v: &[M::Set]) -> usize {
+        if hi - lo <= 1 {
+            let val = if lo < hi { v[lo] } else { self.monoid.id() };
+            self.nodes.push(PersistentSegTreeNode {
+                val,
+                left: 0,
+                right: 0,
+            });
+            return self.nodes.len() - 1;
+        }
+        let mid = lo + (hi - lo) / 2;
+        let left = self.build(lo, mid, v);
+        let right = self.build(mid, hi, v);
+        let val = self.monoid.op(self.nodes[left].val, self.nodes[right].val);
+        self.nodes.push(PersistentSegTreeNode { val, left, right });
+        self.nodes.len() - 1
+    }
+
+    fn set_rec(&mut self, k: usize, lo: usize, hi: usize, i: usize, val: M::Set) -> usize {
+        if hi - lo == 1 {
+            self.nodes.push(PersistentSegTreeNode {
+                val,
+                left: 0,
+                right: 0,
+            });
+            return self.nodes.len() - 1;
+        }
+        let mid = lo + (hi - lo) / 2;
+        let (mut left, mut right) = (self.nodes[k].left, self.nodes[k].right);
+        if i < mid {
+            left = self.set_rec(left, lo, mid, i, val);
+        } else {
+            right = self.set_rec(right, mid, hi, i, val);
+        }
+        let val = self.monoid.op(self.nodes[left].val, self.nodes[right].val);
+        self.nodes.push(PersistentSegTreeNode { val, left, right });
+        self.nodes.len() - 1
+    }
+
+    fn fold_rec(&self, k: usize, lo: usize, hi: usize, l: usize, r: usize) -> M::Set {
+        if r <= lo || hi <= l {
+            return self.monoid.id();
+        }
+        if l <= lo && hi <= r {
+            return self.nodes[k].val;
+        }
+        let mid = lo + (hi - lo) / 2;
+        self.monoid.op(
+            self.fold_rec(self.nodes[k].left, lo, mid, l, r),
+            self.fold_rec(self.nodes[k].right, mid, hi, l, r),
+        )
+    }
+
+    /// バージョン `ver` の `i` 番目の要素を `val` に更新したバージョンを返す。
+    pub fn set(&mut self, ver: Version, i: usize, val: M::Set) -> Version {
+        assert!(i < self.len);
+        Version(self.set_rec(ver.0, 0, self.len, i, val))
+    }
+
+    /// バージョン `ver` の `i` 番目の要素を返す。
+    pub fn get(&self, ver: Version, i: usize) -> M::Set {
+        assert!(i < self.len);
+        let (mut k, mut lo, mut hi) = (ver.0, 0, self.len);
+        while hi - lo > 1 {
+            let mid = lo + (hi - lo) / 2;
+            if i < mid {
+                k = self.nodes[k].left;
+                hi = mid;
+            } else {
+                k = self.nodes[k].right;
+                lo = mid;
+            }
+        }
+        self.nodes[k].val
+    }
+
+    /// バージョン `ver` における半開区間上の積を計算する。
+    pub fn fold(&self, ver: Version, r: Range<usize>) -> M::Set {
+        self.fold_rec(ver.0, 0, self.len, r.start, r.end)
+    }
+}
+
+#[snippet("data_structure/persistent_segtree")]
+impl<M> From<Vec<M::Set>> for PersistentSegTree<M>
+where
+    M: Monoid,
+    M::Set: Clone + Copy,
+{
+    fn from(v: Vec<M::Set>) -> Self {
+        let mut seg = Self {
+            len: v.len(),
+            nodes: Vec::new(),
+            init: Version(0),
+            monoid: M::default(),
+        };
+        let root = seg.build(0, v.len(), &v);
+        seg.init = Version(root);
+        seg
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algebra::op_add::OpAdd;
+    use crate::algebra::op_min::OpMin;
+
+    use super::*;
+
+    #[test]
+    fn test_persistent_segtree() {
+        let mut seg = PersistentSegTree::<OpMin<i32>>::from(vec![5, 3, 8, 6]);
+        let v0 = seg.init();
+        let v1 = seg.set(v0, 1, 9);
+        let v2 = seg.set(v1, 3, 1);
+        let v3 = seg.set(v0, 0, 2);
+
+        assert_eq!(seg.fold(v0, 0..4), 3);
+        assert_eq!(seg.fold(v1, 0..4), 5);
+        assert_eq!(seg.fold(v2, 0..4), 1);
+        assert_eq!(seg.fold(v2, 0..3), 5);
+        assert_eq!(seg.fold(v3, 0..4), 2);
+        assert_eq!(seg.fold(v3, 1..4), 3);
+        assert_eq!(seg.fold(v3, 1..1), std::i32::MAX);
+
+        assert_eq!(seg.get(v0, 1), 3);
+        assert_eq!(seg.get(v1, 1), 9);
+        assert_eq!(seg.get(v3, 1), 3);
+    }
+
+    #[test]
+    fn test_persistent_segtree_naive() {
+        let n = 7;
+        let mut seg = PersistentSegTree::<OpAdd<usize>>::new(n);
+        let mut versions = vec![(seg.init(), vec![0; n])];
+        for t in 0..30 {
+            let (ver, ref v) = versions[(t * 7) % versions.len()];
+            let mut v = v.clone();
+            let i = (t * 5) % n;
+            v[i] = t;
+            let ver = seg.set(ver, i, t);
+            versions.push((ver, v));
+        }
+        for (ver, v) in &versions {
+            for l in 0..=n {
+                for r in l..=n {
+                    assert_eq!(seg.fold(*ver, l..r), v[l..r].iter().sum());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_persistent_segtree_kth_smallest() {
+        // 区間 [l, r) の k 番目に小さい値を求める
+        let a = vec![5, 1, 4, 1, 3, 9, 2, 6];
+        let mut xs = a.clone();
+        xs.sort();
+        xs.dedup();
+
+        let mut seg = PersistentSegTree::<OpAdd<usize>>::new(xs.len());
+        let mut roots = vec![seg.init()];
+        for x in &a {
+            let i = xs.binary_search(x).unwrap();
+            let root = *roots.last().unwrap();
+            let c = seg.get(root, i);
+            roots.push(seg.set(root, i, c + 1));
+        }
+
+        let kth = |l: usize, r: usize, k: usize| {
+            let (mut lo, mut hi) = (0, xs.len());
+            while hi - lo > 1 {
+                let mid = (lo + hi) / 2;
+                let cnt = seg.fold(roots[r], 0..mid) - seg.fold(roots[l], 0..mid);
+                if cnt <= k {
+                    lo = mid;
+                } else {
+                    hi = mid;
+                }
+            }
+            xs[lo]
+        };
+
+        for l in 0..a.len() {
+            for r in l + 1..=a.len() {
+                let mut b = a[l..r].to_vec();
+                b.sort();
+                for (k, x) in b.iter().enumerate() {
+                    assert_eq!(kth(l, r, k), *x);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_persistent_segtree_set_out_of_range() {
+        let mut seg = PersistentSegTree::<OpAdd<i64>>::new(0);
+        let init = seg.init();
+        seg.set(init, 0, 1);
+    }
+}
@@ -0,0 +1,125 @@
+//! Radix Heap。
+use cargo_snippet::snippet;
+
+/// Radix Heap
+///
+/// 取り出されるキーが単調非減少となる場合に使える最小ヒープ。
+/// 最後に取り出したキー以上のキーしか追加できない代わりに、
+/// 追加を `O(1)`、取り出しを償却 `O(log C)` (`C` はキーの最大値) で行うことができる。
+#[snippet("data_structure/radix_heap")]
+#[derive(Debug, Clone)]
+pub struct RadixHeap<K, V> {
+    len: usize,
+    last: K,
+    buckets: Vec<Vec<(K, V)>>,
+}
+
+#[snippet("data_structure/radix_heap")]
+macro_rules! radix_heap_impl {
+    ($($t:ty)*) => ($(
+        impl<V> Default for RadixHeap<$t, V> {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl<V> RadixHeap<$t, V> {
+            /// 空のヒープを作成する。
+            pub fn new() -> Self {
+                Self {
+                    len: 0,
+                    last: 0,
+                    buckets: (0..=std::mem::size_of::<$t>() * 8).map(|_| Vec::new()).collect(),
+                }
+            }
+
+            /// 要素数を返す。
+            pub fn len(&self) -> usize {
+                self.len
+            }
+
+            /// 空かどうかを返す。
+            pub fn is_empty(&self) -> bool {
+                self.len == 0
+            }
+
+            fn bucket(&self, key: $t) -> usize {
+                std::mem::size_of::<$t>() * 8 - (key ^ self.last).leading_zeros() as usize
+            }
+
+            /// キー `key`、値 `val` の要素を追加する。
+            /// `key` は最後に取り出したキー以上である必要がある。
+            pub fn push(&mut self, key: $t, val: V) {
+                assert!(key >= self.last, "key must not be less than the last popped key");
+                let i = self.bucket(key);
+                self.buckets[i].push((key, val));
+                self.len += 1;
+            }
+
+            /// キーが最小の要素を取り出す。
+            pub fn pop(&mut self) -> Option<($t, V)> {
+                if self.len == 0 {
+                    return None;
+                }
+                if self.buckets[0].is_empty() {
+                    let i = self.buckets.iter().position(|b| !b.is_empty()).unwrap();
+                    let bucket = std::mem::take(&mut self.buckets[i]);
+                    self.last = bucket.iter().map(|&(k, _)| k).min().unwrap();
+                    for (k, v) in bucket {
+                        let j = self.bucket(k);
+                        self.buckets[j].push((k, v));
+                    }
+                }
+                self.len -= 1;
+                self.buckets[0].pop()
+            }
+        }
+    )*)
+}
+
+#[snippet("data_structure/radix_heap")]
+radix_heap_impl! { usize u64 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_rng::XorShift;
+    use std::cmp::Reverse;
+    use std::collections::BinaryHeap;
+
+    #[test]
+    fn test_radix_heap() {
+        let mut rng = XorShift::default();
+        let mut heap = RadixHeap::<u64, usize>::new();
+        let mut naive = BinaryHeap::new();
+        let mut last = 0;
+        for i in 0..5000 {
+            if rng.next() % 3 != 0 || naive.is_empty() {
+                let key = last + rng.next() % 1000;
+                heap.push(key, i);
+                naive.push(Reverse(key));
+            } else {
+                let (key, _) = heap.pop().unwrap();
+                assert_eq!(Reverse(key), naive.pop().unwrap());
+                last = key;
+            }
+            assert_eq!(heap.len(), naive.len());
+        }
+        while let Some(Reverse(key)) = naive.pop() {
+            assert_eq!(heap.pop().map(|(k, _)| k), Some(key));
+        }
+        assert!(heap.is_empty());
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_radix_heap_usize_max() {
+        let mut heap = RadixHeap::<usize, ()>::new();
+        heap.push(std::usize::MAX, ());
+        heap.push(0, ());
+        heap.push(std::usize::MAX / 2, ());
+        assert_eq!(heap.pop(), Some((0, ())));
+        assert_eq!(heap.pop(), Some((std::usize::MAX / 2, ())));
+        assert_eq!(heap.pop(), Some((std::usize::MAX, ())));
+    }
+}
@@ -0,0 +1,117 @@
+//! 巻き戻し可能な Union-Find木。
+use cargo_snippet::snippet;
+
+/// 巻き戻し可能な Union-Find木。
+///
+/// 経路圧縮を行わず union by size のみを行うため、各操作は `O(log N)` となる。
+#[snippet("data_structure/rollback_union_find")]
+#[derive(Debug, Clone)]
+pub struct RollbackUnionFind {
+    par: Vec<usize>,
+    size: Vec<usize>,
+    // 各 union で子として繋いだ代表元とその連結成分のサイズ (連結済みの場合は None)
+    history: Vec<Option<(usize, usize)>>,
+}
+
+#[snippet("data_structure/rollback_union_find")]
+impl RollbackUnionFind {
+    /// 大きさ `n` の Union-Find 木を初期化する。
+    pub fn new(n: usize) -> Self {
+        Self {
+            par: vec![0; n],
+            size: vec![1; n],
+            history: Vec::new(),
+        }
+    }
+
+    /// 頂点 `a` の属する連結成分の代表元を返す。
+    pub fn find_root(&self, a: usize) -> usize {
+        let mut a = a;
+        while self.size[a] == 0 {
+            a = self.par[a];
+        }
+        a
+    }
+
+    /// 辺 `(a, b)` を追加し、追加後の連結成分の代表元を返す。
+    pub fn union(&mut self, a: usize, b: usize) -> usize {
+        let mut x = self.find_root(a);
+        let mut y = self.find_root(b);
+        if x == y {
+            self.history.push(None);
+            return x;
+        }
+        if self.size[x] < self.size[y] {
+            std::mem::swap(&mut x, &mut y);
+        }
+        self.history.push(Some((y, self.size[y])));
+        self.size[x] += self.size[y];
+        self.size[y] = 0;
+        self.par[y] = x;
+        x
+    }
+
+    /// 直前の `union` を取り消す。
+    pub fn undo(&mut self) {
+        if let Some((y, s)) = self.history.pop().expect("no union to undo") {
+            let x = self.par[y];
+            self.size[x] -= s;
+            self.size[y] = s;
+        }
+    }
+
+    /// 現在の状態を表す値を返す。`rollback` に渡すことでこの状態に戻すことができる。
+    pub fn snapshot(&self) -> usize {
+        self.history.len()
+    }
+
+    /// `snapshot` を取得した時点の状態に戻す。
+    pub fn rollback(&mut self, snapshot: usize) {
+        while self.history.len() > snapshot {
+            self.undo();
+        }
+    }
+
+    /// 頂点 `a` と 頂点 `b` が同じ連結成分に属しているかを返す。
+    pub fn in_same_set(&self, a: usize, b: usize) -> bool {
+        self.find_root(a) == self.find_root(b)
+    }
+
+    /// 頂点 `a` の属する連結成分のサイズを返す。
+    pub fn group_size(&self, a: usize) -> usize {
+        self.size[self.find_root(a)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rollback_union_find() {
+        let mut uf = RollbackUnionFind::new(6);
+        uf.union(0, 1);
+        let s0 = uf.snapshot();
+        uf.union(2, 3);
+        uf.union(1, 3);
+        assert!(uf.in_same_set(0, 2));
+        assert_eq!(uf.group_size(3), 4);
+        let s1 = uf.snapshot();
+        uf.union(0, 2);
+        uf.union(4, 5);
+        assert_eq!(uf.group_size(5), 2);
+
+        uf.undo();
+        assert!(!uf.in_same_set(4, 5));
+        uf.rollback(s1);
+        assert!(uf.in_same_set(0, 2));
+        assert_eq!(uf.group_size(0), 4);
+        uf.rollback(s0);
+        assert!(uf.in_same_set(0, 1));
+        assert!(!uf.in_same_set(0, 2));
+        assert!(!uf.in_same_set(2, 3));
+        assert_eq!(uf.group_size(0), 2);
+        assert_eq!(uf.group_size(2), 1);
+        assert_eq!(uf.group_size(3), 1);
+    }
+}
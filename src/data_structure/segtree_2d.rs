@@ -0,0 +1,147 @@
+//! 二次元セグメント木。
+use cargo_snippet::snippet;
+
+#[snippet("data_structure/segtree_2d")]
+use std::ops::Range;
+
+use super::super::algebra::structure::CommutativeGroup;
+use super::fenwick::FenwickTree;
+use super::traits::{Fold, Fold2D};
+use crate::utils::binary_search::BinarySearch;
+use crate::utils::integer::ceil_pow2;
+
+/// 二次元セグメント木。
+///
+/// 更新する点の座標を先読みし、x 座標についてのセグメント木の各ノードに
+/// そのノードに属する点の y 座標でのフェニック木を持たせたデータ構造。
+/// 可換群に対して、
+/// - 点 (あらかじめ与えたもの) への加算
+/// - 矩形領域の和の取得
+///
+/// を `O(log^2 N)` で行うことができる。
+#[snippet("data_structure/segtree_2d")]
+pub struct SegTree2D<G: CommutativeGroup> {
+    size: usize,
+    xs: Vec<usize>,
+    ys: Vec<Vec<usize>>,
+    data: Vec<FenwickTree<G>>,
+    group: G,
+}
+
+#[snippet("data_structure/segtree_2d")]
+impl<G> SegTree2D<G>
+where
+    G: CommutativeGroup,
+    G::Set: Clone + Copy,
+{
+    /// 加算を行う点の座標の一覧から二次元セグメント木を作成する。
+    pub fn new(points: &[(usize, usize)]) -> Self {
+        let group = G::default();
+        let mut xs: Vec<_> = points.iter().map(|&(x, _)| x).collect();
+        xs.sort_unstable();
+        xs.dedup();
+        let size = 1 << ceil_pow2(xs.len());
+        let mut ys = vec![Vec::new(); size << 1];
+        for &(x, y) in points {
+            let i = xs.search_sorted_first(&x);
+            ys[size + i].push(y);
+        }
+        for i in (1..(size << 1)).rev() {
+            if i < size {
+                let mut v = ys[i << 1].clone();
+                v.extend(ys[(i << 1) + 1].iter());
+                ys[i] = v;
+            }
+            ys[i].sort_unstable();
+            ys[i].dedup();
+        }
+        let data = ys.iter().map(|v| FenwickTree::new(v.len())).collect();
+        Self {
+            size,
+            xs,
+            ys,
+            data,
+            group,
+        }
+    }
+
+    /// 点 `(x, y)` の値に `val` を加算する。`(x, y)` は作成時に与えた点でなければならない。
+    pub fn add(&mut self, x: usize, y: usize, val: G::Set) {
+        let mut k = self.size + self.xs.search_sorted_first(&x);
+        while k > 0 {
+            let j = self.ys[k].search_sorted_first(&y);
+            self.data[k].add(j, val);
+            k >>= 1;
+        }
+    }
+
+    /// ノード `k` における y 座標が `ry` に含まれる点の値の和を計算する。
+    fn fold_node(&self, k: usize, ry: &Range<usize>) -> G::Set {
+        let l = self.ys[k].search_sorted_first(&ry.start);
+        let r = self.ys[k].search_sorted_first(&ry.end);
+        self.data[k].fold(l..r)
+    }
+}
+
+#[snippet("data_structure/segtree_2d")]
+impl<G> Fold2D for SegTree2D<G>
+where
+    G: CommutativeGroup,
+    G::Set: Clone + Copy,
+{
+    type Output = G::Set;
+
+    /// 矩形領域上の和を計算する。
+    fn fold(&self, rx: Range<usize>, ry: Range<usize>) -> G::Set {
+        let mut l = self.size + self.xs.search_sorted_first(&rx.start);
+        let mut r = self.size + self.xs.search_sorted_first(&rx.end);
+        let mut s = self.group.id();
+        while l < r {
+            if l & 1 == 1 {
+                s = self.group.op(s, self.fold_node(l, &ry));
+                l += 1;
+            }
+            if r & 1 == 1 {
+                r -= 1;
+                s = self.group.op(s, self.fold_node(r, &ry));
+            }
+            l >>= 1;
+            r >>= 1;
+        }
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algebra::op_add::OpAdd;
+
+    #[test]
+    fn test_segtree_2d() {
+        let points: Vec<_> = (0..40).map(|i| ((i * 37) % 23, (i * 13) % 17)).collect();
+        let mut seg = SegTree2D::<OpAdd<i64>>::new(&points);
+        let mut values = vec![0; points.len()];
+        for (i, &(x, y)) in points.iter().enumerate() {
+            let v = (i as i64 * 7) % 10 - 3;
+            seg.add(x, y, v);
+            values[i] += v;
+        }
+
+        for x0 in 0..=24 {
+            for x1 in x0..=24 {
+                for y0 in (0..=18).step_by(3) {
+                    for y1 in y0..=18 {
+                        let mut s = 0;
+                        for (i, &(x, y)) in points.iter().enumerate() {
+                            if x0 <= x && x < x1 && y0 <= y && y < y1 {
+                                s += values[i];
+                            }
+                        }
+                        assert_eq!(seg.fold(x0..x1, y0..y1), s);
+                    }
+                }
+            }
+        }
+    }
+}
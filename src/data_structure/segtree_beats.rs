@@ -0,0 +1,299 @@
+//! Segment Tree Beats。
+use cargo_snippet::snippet;
+
+#[snippet("data_structure/segtree_beats")]
+use std::ops::Range;
+
+/// Segment Tree Beats。
+///
+/// 整数列に対して、
+/// - 区間 chmin (`a[i] = min(a[i], x)`)
+/// - 区間 chmax (`a[i] = max(a[i], x)`)
+/// - 区間加算
+/// - 区間和・区間最小値・区間最大値の取得
+///
+/// を償却 `O(log^2 N)` で行うことができる。
+#[snippet("data_structure/segtree_beats")]
+#[derive(Debug, Clone)]
+pub struct SegTreeBeats {
+    n: usize,
+    max1: Vec<i64>,
+    max2: Vec<i64>,
+    max_cnt: Vec<i64>,
+    min1: Vec<i64>,
+    min2: Vec<i64>,
+    min_cnt: Vec<i64>,
+    sum: Vec<i64>,
+    len: Vec<i64>,
+    lazy: Vec<i64>,
+}
+
+#[snippet("data_structure/segtree_beats")]
+impl SegTreeBeats {
+    /// 全ての要素が 0 である長さ `n` の Segment Tree Beats を作成する。
+    pub fn new(n: usize) -> Self {
+        Self::from(vec![0; n])
+    }
+
+    fn build(&mut self, k: usize, lo: usize, hi: usize, v: &[i64]) {
+        if hi - lo == 1 {
+            self.max1[k] = v[lo];
+            self.min1[k] = v[lo];
+            self.max_cnt[k] = 1;
+            self.min_cnt[k] = 1;
+            self.sum[k] = v[lo];
+            self.len[k] = 1;
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        self.build(k << 1, lo, mid, v);
+        self.build((k << 1) + 1, mid, hi, v);
+        self.update(k);
+    }
+
+    fn update(&mut self, k: usize) {
+        let (l, r) = (k << 1, (k << 1) + 1);
+        self.sum[k] = self.sum[l] + self.sum[r];
+        self.len[k] = self.len[l] + self.len[r];
+
+        if self.max1[l] > self.max1[r] {
+            self.max1[k] = self.max1[l];
+            self.max_cnt[k] = self.max_cnt[l];
+            self.max2[k] = self.max2[l].max(self.max1[r]);
+        } else if self.max1[l] < self.max1[r] {
+            self.max1[k] = self.max1[r];
+            self.max_cnt[k] = self.max_cnt[r];
+            self.max2[k] = self.max1[l].max(self.max2[r]);
+        } else {
+            self.max1[k] = self.max1[l];
+            self.max_cnt[k] = self.max_cnt[l] + self.max_cnt[r];
+            self.max2[k] = self.max2[l].max(self.max2[r]);
+        }
+
+        if self.min1[l] < self.min1[r] {
+            self.min1[k] = self.min1[l];
+            self.min_cnt[k] = self.min_cnt[l];
+            self.min2[k] = self.min2[l].min(self.min1[r]);
+        } else if self.min1[l] > self.min1[r] {
+            self.min1[k] = self.min1[r];
+            self.min_cnt[k] = self.min_cnt[r];
+            self.min2[k] = self.min1[l].min(self.min2[r]);
+        } else {
+            self.min1[k] = self.min1[l];
+            self.min_cnt[k] = self.min_cnt[l] + self.min_cnt[r];
+            self.min2[k] = self.min2[l].min(self.min2[r]);
+        }
+    }
+
+    /// ノード `k` の最大値を `x` (`max2 < x < max1`) に下げる。
+    fn apply_chmin(&mut self, k: usize, x: i64) {
+        self.sum[k] += (x - self.max1[k]) * self.max_cnt[k];
+        if self.max1[k] == self.min1[k] {
+            self.min1[k] = x;
+        } else if self.max1[k] == self.min2[k] {
+            self.min2[k] = x;
+        }
+        self.max1[k] = x;
+    }
+
+    /// ノード `k` の最小値を `x` (`min1 < x < min2`) に上げる。
+    fn apply_chmax(&mut self, k: usize, x: i64) {
+        self.sum[k] += (x - self.min1[k]) * self.min_cnt[k];
+        if self.min1[k] == self.max1[k] {
+            self.max1[k] = x;
+        } else if self.min1[k] == self.max2[k] {
+            self.max2[k] = x;
+        }
+        self.min1[k] = x;
+    }
+
+    fn apply_add(&mut self, k: usize, x: i64) {
+        self.max1[k] += x;
+        if self.max2[k] != std::i64::MIN {
+            self.max2[k] += x;
+        }
+        self.min1[k] += x;
+        if self.min2[k] != std::i64::MAX {
+            self.min2[k] += x;
+        }
+        self.sum[k] += self.len[k] * x;
+        self.lazy[k] += x;
+    }
+
+    fn push(&mut self, k: usize) {
+        for c in (k << 1)..=(k << 1) + 1 {
+            if self.lazy[k] != 0 {
+                self.apply_add(c, self.lazy[k]);
+            }
+            if self.max1[c] > self.max1[k] {
+                self.apply_chmin(c, self.max1[k]);
+            }
+            if self.min1[c] < self.min1[k] {
+                self.apply_chmax(c, self.min1[k]);
+            }
+        }
+        self.lazy[k] = 0;
+    }
+
+    fn chmin_rec(&mut self, k: usize, lo: usize, hi: usize, r: &Range<usize>, x: i64) {
+        if hi <= r.start || r.end <= lo || self.max1[k] <= x {
+            return;
+        }
+        if r.start <= lo && hi <= r.end && self.max2[k] < x {
+            self.apply_chmin(k, x);
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        self.push(k);
+        self.chmin_rec(k << 1, lo, mid, r, x);
+        self.chmin_rec((k << 1) + 1, mid, hi, r, x);
+        self.update(k);
+    }
+
+    fn chmax_rec(&mut self, k: usize, lo: usize, hi: usize, r: &Range<usize>, x: i64) {
+        if hi <= r.start || r.end <= lo || self.min1[k] >= x {
+            return;
+        }
+        if r.start <= lo && hi <= r.end && self.min2[k] > x {
+            self.apply_chmax(k, x);
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        self.push(k);
+        self.chmax_rec(k << 1, lo, mid, r, x);
+        self.chmax_rec((k << 1) + 1, mid, hi, r, x);
+        self.update(k);
+    }
+
+    fn add_rec(&mut self, k: usize, lo: usize, hi: usize, r: &Range<usize>, x: i64) {
+        if hi <= r.start || r.end <= lo {
+            return;
+        }
+        if r.start <= lo && hi <= r.end {
+            self.apply_add(k, x);
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        self.push(k);
+        self.add_rec(k << 1, lo, mid, r, x);
+        self.add_rec((k << 1) + 1, mid, hi, r, x);
+        self.update(k);
+    }
+
+    /// 区間 `r` 上で `(sum, min, max)` を計算する。
+    fn fold_rec(&mut self, k: usize, lo: usize, hi: usize, r: &Range<usize>) -> (i64, i64, i64) {
+        if hi <= r.start || r.end <= lo {
+            return (0, std::i64::MAX, std::i64::MIN);
+        }
+        if r.start <= lo && hi <= r.end {
+            return (self.sum[k], self.min1[k], self.max1[k]);
+        }
+        let mid = lo + (hi - lo) / 2;
+        self.push(k);
+        let (s0, min0, max0) = self.fold_rec(k << 1, lo, mid, r);
+        let (s1, min1, max1) = self.fold_rec((k << 1) + 1, mid, hi, r);
+        (s0 + s1, min0.min(min1), max0.max(max1))
+    }
+
+    /// 区間 `r` の各要素を `a[i] = min(a[i], x)` で更新する。
+    pub fn chmin(&mut self, r: Range<usize>, x: i64) {
+        self.chmin_rec(1, 0, self.n, &r, x);
+    }
+
+    /// 区間 `r` の各要素を `a[i] = max(a[i], x)` で更新する。
+    pub fn chmax(&mut self, r: Range<usize>, x: i64) {
+        self.chmax_rec(1, 0, self.n, &r, x);
+    }
+
+    /// 区間 `r` の各要素に `x` を加算する。
+    pub fn add(&mut self, r: Range<usize>, x: i64) {
+        self.add_rec(1, 0, self.n, &r, x);
+    }
+
+    /// 区間 `r` の要素の和を返す。
+    pub fn sum(&mut self, r: Range<usize>) -> i64 {
+        self.fold_rec(1, 0, self.n, &r).0
+    }
+
+    /// 区間 `r` の要素の最小値を返す。区間が空の場合は `std::i64::MAX` を返す。
+    pub fn min(&mut self, r: Range<usize>) -> i64 {
+        self.fold_rec(1, 0, self.n, &r).1
+    }
+
+    /// 区間 `r` の要素の最大値を返す。区間が空の場合は `std::i64::MIN` を返す。
+    pub fn max(&mut self, r: Range<usize>) -> i64 {
+        self.fold_rec(1, 0, self.n, &r).2
+    }
+}
+
+#[snippet("data_structure/segtree_beats")]
+impl From<Vec<i64>> for SegTreeBeats {
+    fn from(v: Vec<i64>) -> Self {
+        let n = v.len();
+        let m = 4 * n.max(1);
+        let mut seg = Self {
+            n,
+            max1: vec![std::i64::MIN; m],
+            max2: vec![std::i64::MIN; m],
+            max_cnt: vec![0; m],
+            min1: vec![std::i64::MAX; m],
+            min2: vec![std::i64::MAX; m],
+            min_cnt: vec![0; m],
+            sum: vec![0; m],
+            len: vec![0; m],
+            lazy: vec![0; m],
+        };
+        if n > 0 {
+            seg.build(1, 0, n, &v);
+        }
+        seg
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_rng::XorShift;
+
+    #[test]
+    fn test_segtree_beats() {
+        let mut rng = XorShift::default();
+        for n in 1..=20 {
+            let mut a: Vec<i64> = (0..n).map(|_| (rng.next() % 200) as i64 - 100).collect();
+            let mut seg = SegTreeBeats::from(a.clone());
+            for _ in 0..200 {
+                let l = rng.next() as usize % (n + 1);
+                let r = rng.next() as usize % (n + 1);
+                let (l, r) = (l.min(r), l.max(r));
+                let x = (rng.next() % 200) as i64 - 100;
+                match rng.next() % 3 {
+                    0 => {
+                        seg.chmin(l..r, x);
+                        a[l..r].iter_mut().for_each(|v| *v = (*v).min(x));
+                    }
+                    1 => {
+                        seg.chmax(l..r, x);
+                        a[l..r].iter_mut().for_each(|v| *v = (*v).max(x));
+                    }
+                    _ => {
+                        seg.add(l..r, x);
+                        a[l..r].iter_mut().for_each(|v| *v += x);
+                    }
+                }
+                let l = rng.next() as usize % (n + 1);
+                let r = rng.next() as usize % (n + 1);
+                let (l, r) = (l.min(r), l.max(r));
+                let s = &a[l..r];
+                assert_eq!(seg.sum(l..r), s.iter().sum::<i64>());
+                assert_eq!(
+                    seg.min(l..r),
+                    s.iter().copied().min().unwrap_or(std::i64::MAX)
+                );
+                assert_eq!(
+                    seg.max(l..r),
+                    s.iter().copied().max().unwrap_or(std::i64::MIN)
+                );
+            }
+        }
+    }
+}
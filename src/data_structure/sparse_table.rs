@@ -0,0 +1,93 @@
+//! スパーステーブル。
+use cargo_snippet::snippet;
+
+use super::super::algebra::structure::{Idempotent, Monoid};
+use super::traits::Fold;
+
+#[snippet("data_structure/sparse_table")]
+use std::ops::Range;
+
+/// スパーステーブル。
+///
+/// 冪等なモノイド (`OpMin`, `OpMax`, `OpGcd` など) に対して、
+/// 静的な列の区間の要素の積の取得を前計算 `O(N log N)`、クエリ `O(1)` で行うことができる。
+#[snippet("data_structure/sparse_table")]
+pub struct SparseTable<M: Monoid + Idempotent> {
+    table: Vec<Vec<M::Set>>,
+    monoid: M,
+}
+
+#[snippet("data_structure/sparse_table")]
+impl<M> From<Vec<M::Set>> for SparseTable<M>
+where
+    M: Monoid + Idempotent,
+    M::Set: Clone,
+{
+    fn from(v: Vec<M::Set>) -> Self {
+        let monoid = M::default();
+        let n = v.len();
+        let mut table = vec![v];
+        let mut k = 1;
+        while (1 << k) <= n {
+            let prev = &table[k - 1];
+            let half = 1 << (k - 1);
+            let row = (0..=n - (1 << k))
+                .map(|i| monoid.op(prev[i].clone(), prev[i + half].clone()))
+                .collect();
+            table.push(row);
+            k += 1;
+        }
+        Self { table, monoid }
+    }
+}
+
+#[snippet("data_structure/sparse_table")]
+impl<M> Fold for SparseTable<M>
+where
+    M: Monoid + Idempotent,
+    M::Set: Clone,
+{
+    type Output = M::Set;
+
+    fn fold(&self, r: Range<usize>) -> Self::Output {
+        if r.start >= r.end {
+            return self.monoid.id();
+        }
+        let len = r.end - r.start;
+        let k = std::mem::size_of::<usize>() * 8 - len.leading_zeros() as usize - 1;
+        self.monoid.op(
+            self.table[k][r.start].clone(),
+            self.table[k][r.end - (1 << k)].clone(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algebra::op_gcd::OpGcd;
+    use crate::algebra::op_max::OpMax;
+    use crate::algebra::op_min::OpMin;
+    use crate::math::gcd::Gcd;
+
+    #[test]
+    fn test_sparse_table() {
+        for n in 0..=33 {
+            let a: Vec<u64> = (0..n).map(|i| (i * 37 + 11) % 29 * 6).collect();
+            let min = SparseTable::<OpMin<u64>>::from(a.clone());
+            let max = SparseTable::<OpMax<u64>>::from(a.clone());
+            let gcd = SparseTable::<OpGcd<u64>>::from(a.clone());
+            for l in 0..=n as usize {
+                for r in l..=n as usize {
+                    let s = &a[l..r];
+                    assert_eq!(
+                        min.fold(l..r),
+                        s.iter().copied().min().unwrap_or(std::u64::MAX)
+                    );
+                    assert_eq!(max.fold(l..r), s.iter().copied().max().unwrap_or(0));
+                    assert_eq!(gcd.fold(l..r), s.iter().fold(0u64, |g, &x| g.gcd(x)));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,310 @@
+//! Sliding Window Aggregation。
+use cargo_snippet::snippet;
+
+use super::super::algebra::structure::Monoid;
+
+/// Sliding Window Aggregation
+///
+/// 二つのスタックを用いたキュー。
+/// - 末尾への追加
+/// - 先頭の取り出し
+/// - 全体の積の取得
+///
+/// を償却 `O(1)` で行うことができる。逆元を持たないモノイドに対しても利用できる。
+#[snippet("data_structure/swag")]
+pub struct Swag<M: Monoid> {
+    // (値, その値から先頭までの積) を、先頭が末尾に来るように積む
+    front: Vec<(M::Set, M::Set)>,
+    back: Vec<M::Set>,
+    back_sum: M::Set,
+    monoid: M,
+}
+
+#[snippet("data_structure/swag")]
+impl<M> Default for Swag<M>
+where
+    M: Monoid,
+    M::Set: Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[snippet("data_structure/swag")]
+impl<M> Swag<M>
+where
+    M: Monoid,
+    M::Set: Clone,
+{
+    /// 空のキューを作成する。
+    pub fn new() -> Self {
+        let monoid = M::default();
+        Self {
+            front: Vec::new(),
+            back: Vec::new(),
+            back_sum: monoid.id(),
+            monoid,
+        }
+    }
+
+    /// 要素数を返す。
+    pub fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+
+    /// 空かどうかを返す。
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 末尾に `val` を追加する。
+    pub fn push_back(&mut self, val: M::Set) {
+        self.back_sum = self.monoid.op(self.back_sum.clone(), val.clone());
+        self.back.push(val);
+    }
+
+    /// 先頭の要素を取り出す。
+    pub fn pop_front(&mut self) -> Option<M::Set> {
+        if self.front.is_empty() {
+            let mut sum = self.monoid.id();
+            while let Some(val) = self.back.pop() {
+                sum = self.monoid.op(val.clone(), sum);
+                self.front.push((val, sum.clone()));
+            }
+            self.back_sum = self.monoid.id();
+        }
+        self.front.pop().map(|(val, _)| val)
+    }
+
+    /// 先頭から末尾までの要素の積を返す。
+    pub fn fold(&self) -> M::Set {
+        let front = self
+            .front
+            .last()
+            .map_or_else(|| self.monoid.id(), |(_, s)| s.clone());
+        self.monoid.op(front, self.back_sum.clone())
+    }
+}
+
+/// 両端キュー版の Sliding Window Aggregation
+///
+/// - 先頭・末尾への追加
+/// - 先頭・末尾の取り出し
+/// - 全体の積の取得
+///
+/// を償却 `O(1)` で行うことができる。
+#[snippet("data_structure/swag")]
+pub struct SwagDeque<M: Monoid> {
+    // (値, その値から先頭までの積) を、先頭が末尾に来るように積む
+    front: Vec<(M::Set, M::Set)>,
+    // (値, 末尾側の先頭からその値までの積) を、末尾が末尾に来るように積む
+    back: Vec<(M::Set, M::Set)>,
+    monoid: M,
+}
+
+#[snippet("data_structure/swag")]
+impl<M> Default for SwagDeque<M>
+where
+    M: Monoid,
+    M::Set: Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[snippet("data_structure/swag")]
+impl<M> SwagDeque<M>
+where
+    M: Monoid,
+    M::Set: Clone,
+{
+    /// 空の両端キューを作成する。
+    pub fn new() -> Self {
+        Self {
+            front: Vec::new(),
+            back: Vec::new(),
+            monoid: M::default(),
+        }
+    }
+
+    /// 要素数を返す。
+    pub fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+
+    /// 空かどうかを返す。
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn sum(&self, stack: &[(M::Set, M::Set)]) -> M::Set {
+        stack
+            .last()
+            .map_or_else(|| self.monoid.id(), |(_, s)| s.clone())
+    }
+
+    /// 先頭に `val` を追加する。
+    pub fn push_front(&mut self, val: M::Set) {
+        let sum = self.monoid.op(val.clone(), self.sum(&self.front));
+        self.front.push((val, sum));
+    }
+
+    /// 末尾に `val` を追加する。
+    pub fn push_back(&mut self, val: M::Set) {
+        let sum = self.monoid.op(self.sum(&self.back), val.clone());
+        self.back.push((val, sum));
+    }
+
+    /// 先頭から順に並んだ `vals` の前半を先頭側、後半を末尾側のスタックとして積み直す。
+    fn rebuild(&mut self, vals: Vec<M::Set>, front_len: usize) {
+        let mut vals = vals;
+        let back = vals.split_off(front_len);
+        for val in vals.into_iter().rev() {
+            self.push_front(val);
+        }
+        for val in back {
+            self.push_back(val);
+        }
+    }
+
+    /// 先頭の要素を取り出す。
+    pub fn pop_front(&mut self) -> Option<M::Set> {
+        if self.front.is_empty() {
+            let vals: Vec<_> = self.back.drain(..).map(|(val, _)| val).collect();
+            let n = vals.len();
+            self.rebuild(vals, (n + 1) / 2);
+        }
+        self.front.pop().map(|(val, _)| val)
+    }
+
+    /// 末尾の要素を取り出す。
+    pub fn pop_back(&mut self) -> Option<M::Set> {
+        if self.back.is_empty() {
+            let vals: Vec<_> = self.front.drain(..).rev().map(|(val, _)| val).collect();
+            let n = vals.len();
+            self.rebuild(vals, n / 2);
+        }
+        self.back.pop().map(|(val, _)| val)
+    }
+
+    /// 先頭から末尾までの要素の積を返す。
+    pub fn fold(&self) -> M::Set {
+        self.monoid.op(self.sum(&self.front), self.sum(&self.back))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algebra::op_min::OpMin;
+    use crate::algebra::structure::{Associative, Identity, Magma};
+    use crate::data_structure::segtree::SegTree;
+    use crate::data_structure::traits::Fold;
+    use crate::utils::test_rng::XorShift;
+    use std::collections::VecDeque;
+
+    // 2x2 行列の積 (非可換)
+    #[derive(Default)]
+    struct OpMatMul;
+
+    impl Magma for OpMatMul {
+        type Set = [[i64; 2]; 2];
+
+        fn op(&self, x: Self::Set, y: Self::Set) -> Self::Set {
+            let mut z = [[0; 2]; 2];
+            for i in 0..2 {
+                for j in 0..2 {
+                    for k in 0..2 {
+                        z[i][j] = (z[i][j] + x[i][k] * y[k][j]) % 998244353;
+                    }
+                }
+            }
+            z
+        }
+    }
+
+    impl Associative for OpMatMul {}
+
+    impl Identity for OpMatMul {
+        fn id(&self) -> Self::Set {
+            [[1, 0], [0, 1]]
+        }
+    }
+
+    fn random_matrix(rng: &mut XorShift) -> [[i64; 2]; 2] {
+        let mut x = [[0; 2]; 2];
+        x.iter_mut()
+            .flatten()
+            .for_each(|v| *v = (rng.next() % 10) as i64);
+        x
+    }
+
+    fn naive_fold(q: &VecDeque<[[i64; 2]; 2]>) -> [[i64; 2]; 2] {
+        let op = OpMatMul;
+        q.iter().fold(op.id(), |s, &x| op.op(s, x))
+    }
+
+    #[test]
+    fn test_swag_min_matches_segtree() {
+        let mut rng = XorShift::default();
+        let a: Vec<i64> = (0..200).map(|_| (rng.next() % 1000) as i64).collect();
+        let seg = SegTree::<OpMin<i64>>::from(a.clone());
+        let mut swag = Swag::<OpMin<i64>>::new();
+        let (mut l, mut r) = (0, 0);
+        while r < a.len() {
+            if l == r || rng.next() % 2 == 0 {
+                swag.push_back(a[r]);
+                r += 1;
+            } else {
+                assert_eq!(swag.pop_front(), Some(a[l]));
+                l += 1;
+            }
+            assert_eq!(swag.len(), r - l);
+            assert_eq!(swag.fold(), seg.fold(l..r));
+        }
+    }
+
+    #[test]
+    fn test_swag_matrix() {
+        let mut rng = XorShift::default();
+        let mut swag = Swag::<OpMatMul>::new();
+        let mut naive = VecDeque::new();
+        for _ in 0..1000 {
+            if rng.next() % 3 != 0 {
+                let x = random_matrix(&mut rng);
+                swag.push_back(x);
+                naive.push_back(x);
+            } else {
+                assert_eq!(swag.pop_front(), naive.pop_front());
+            }
+            assert_eq!(swag.fold(), naive_fold(&naive));
+        }
+    }
+
+    #[test]
+    fn test_swag_deque() {
+        let mut rng = XorShift::default();
+        let mut swag = SwagDeque::<OpMatMul>::new();
+        let mut naive = VecDeque::new();
+        for _ in 0..3000 {
+            match rng.next() % 4 {
+                0 => {
+                    let x = random_matrix(&mut rng);
+                    swag.push_front(x);
+                    naive.push_front(x);
+                }
+                1 => {
+                    let x = random_matrix(&mut rng);
+                    swag.push_back(x);
+                    naive.push_back(x);
+                }
+                2 => assert_eq!(swag.pop_front(), naive.pop_front()),
+                _ => assert_eq!(swag.pop_back(), naive.pop_back()),
+            }
+            assert_eq!(swag.len(), naive.len());
+            assert_eq!(swag.fold(), naive_fold(&naive));
+        }
+    }
+}
@@ -35,3 +35,12 @@ pub trait BisectFoldRev<S> {
     where
         F: Fn(&S) -> bool;
 }
+
+/// 矩形領域上の Fold 演算
+#[snippet("data_structure/traits")]
+pub trait Fold2D {
+    type Output;
+
+    /// `rx` × `ry` の矩形領域上で fold 演算を行う。
+    fn fold(&self, rx: Range<usize>, ry: Range<usize>) -> Self::Output;
+}
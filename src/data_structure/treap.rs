@@ -0,0 +1,600 @@
+//! Treap。
+use cargo_snippet::snippet;
+
+#[snippet("data_structure/treap")]
+use std::mem::swap;
+#[snippet("data_structure/treap")]
+use std::ops::Range;
+
+use super::super::algebra::structure::{Monoid, MonoidAction};
+use super::traits::Fold;
+
+#[snippet("data_structure/treap")]
+type Link<K, S, F> = Option<Box<TreapNode<K, S, F>>>;
+#[snippet("data_structure/treap")]
+type LinkPair<K, S, F> = (Link<K, S, F>, Link<K, S, F>);
+
+/// Treap のノード
+///
+/// `val`, `sum`, `sum_rev` は自身の遅延評価を反映済みの値を持ち、
+/// `lazy`, `rev` は子に対して未評価の作用・反転を表す。
+#[snippet("data_structure/treap")]
+struct TreapNode<K, S, F> {
+    key: K,
+    val: S,
+    sum: S,
+    sum_rev: S,
+    lazy: F,
+    rev: bool,
+    size: usize,
+    priority: u64,
+    left: Link<K, S, F>,
+    right: Link<K, S, F>,
+}
+
+#[snippet("data_structure/treap")]
+impl<K, S, F> TreapNode<K, S, F> {
+    /// 反転が `flip` だけ未評価であるときの、真の並び順における子を `(左, 右)` の順で返す。
+    fn children(&self, flip: bool) -> (&Link<K, S, F>, &Link<K, S, F>) {
+        if flip {
+            (&self.right, &self.left)
+        } else {
+            (&self.left, &self.right)
+        }
+    }
+}
+
+/// Treap
+///
+/// キーの昇順に要素を管理する平衡二分探索木。
+/// 各要素はキー `K` とモノイド `M` の値を持ち、
+/// - 要素の挿入・削除
+/// - `k` 番目の要素・キーの順位の取得
+/// - 分割・併合
+/// - 位置による区間の積の取得・区間作用・区間反転
+///
+/// を期待計算量 `O(log N)` で行うことができる。
+/// 区間反転を行うとキーの昇順は保たれないため、以降はキーによる操作を行ってはならない。
+#[snippet("data_structure/treap")]
+pub struct Treap<K, M: Monoid, X: MonoidAction<M>> {
+    root: Link<K, M::Set, X::Set>,
+    seed: u64,
+    monoid: M,
+    action: X,
+}
+
+#[snippet("data_structure/treap")]
+impl<K, M, X> Default for Treap<K, M, X>
+where
+    K: Ord,
+    M: Monoid,
+    M::Set: Clone,
+    X: MonoidAction<M>,
+    X::Set: Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[snippet("data_structure/treap")]
+impl<K, M, X> Treap<K, M, X>
+where
+    K: Ord,
+    M: Monoid,
+    M::Set: Clone,
+    X: MonoidAction<M>,
+    X::Set: Clone,
+{
+    /// 空の Treap を作成する。
+    pub fn new() -> Self {
+        Self::with_seed(88172645463325252)
+    }
+
+    fn with_seed(seed: u64) -> Self {
+        Self {
+            root: None,
+            seed,
+            monoid: M::default(),
+            action: X::default(),
+        }
+    }
+
+    /// 要素数を返す。
+    pub fn len(&self) -> usize {
+        Self::size(&self.root)
+    }
+
+    /// 空かどうかを返す。
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    fn next_priority(&mut self) -> u64 {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 7;
+        self.seed ^= self.seed << 17;
+        self.seed
+    }
+
+    fn size(t: &Link<K, M::Set, X::Set>) -> usize {
+        t.as_ref().map_or(0, |t| t.size)
+    }
+
+    fn sum(&self, t: &Link<K, M::Set, X::Set>) -> M::Set {
+        t.as_ref()
+            .map_or_else(|| self.monoid.id(), |t| t.sum.clone())
+    }
+
+    fn sum_rev(&self, t: &Link<K, M::Set, X::Set>) -> M::Set {
+        t.as_ref()
+            .map_or_else(|| self.monoid.id(), |t| t.sum_rev.clone())
+    }
+
+    fn update(&self, t: &mut TreapNode<K, M::Set, X::Set>) {
+        t.size = Self::size(&t.left) + Self::size(&t.right) + 1;
+        t.sum = self.monoid.op(
+            self.monoid.op(self.sum(&t.left), t.val.clone()),
+            self.sum(&t.right),
+        );
+        t.sum_rev = self.monoid.op(
+            self.monoid.op(self.sum_rev(&t.right), t.val.clone()),
+            self.sum_rev(&t.left),
+        );
+    }
+
+    fn apply_act(&self, t: &mut TreapNode<K, M::Set, X::Set>, f: X::Set) {
+        t.val = self.action.act(t.val.clone(), f.clone());
+        t.sum = self.action.act(t.sum.clone(), f.clone());
+        t.sum_rev = self.action.act(t.sum_rev.clone(), f.clone());
+        t.lazy = self.action.op(t.lazy.clone(), f);
+    }
+
+    fn apply_rev(t: &mut TreapNode<K, M::Set, X::Set>) {
+        swap(&mut t.sum, &mut t.sum_rev);
+        t.rev ^= true;
+    }
+
+    fn push(&self, t: &mut TreapNode<K, M::Set, X::Set>) {
+        if t.rev {
+            swap(&mut t.left, &mut t.right);
+            for c in t.left.iter_mut().chain(t.right.iter_mut()) {
+                Self::apply_rev(c);
+            }
+            t.rev = false;
+        }
+        let f = std::mem::replace(&mut t.lazy, self.action.id());
+        for c in t.left.iter_mut().chain(t.right.iter_mut()) {
+            self.apply_act(c, f.clone());
+        }
+    }
+
+    fn merge_link(
+        &self,
+        a: Link<K, M::Set, X::Set>,
+        b: Link<K, M::Set, X::Set>,
+    ) -> Link<K, M::Set, X::Set> {
+        match (a, b) {
+            (None, t) | (t, None) => t,
+            (Some(mut a), Some(mut b)) => {
+                if a.priority > b.priority {
+                    self.push(&mut a);
+                    a.right = self.merge_link(a.right.take(), Some(b));
+                    self.update(&mut a);
+                    Some(a)
+                } else {
+                    self.push(&mut b);
+                    b.left = self.merge_link(Some(a), b.left.take());
+                    self.update(&mut b);
+                    Some(b)
+                }
+            }
+        }
+    }
+
+    /// 先頭 `k` 個とそれ以外に分割する。
+    fn split_at(&self, t: Link<K, M::Set, X::Set>, k: usize) -> LinkPair<K, M::Set, X::Set> {
+        let mut t = match t {
+            Some(t) => t,
+            None => return (None, None),
+        };
+        self.push(&mut t);
+        let ls = Self::size(&t.left);
+        if k <= ls {
+            let (a, b) = self.split_at(t.left.take(), k);
+            t.left = b;
+            self.update(&mut t);
+            (a, Some(t))
+        } else {
+            let (a, b) = self.split_at(t.right.take(), k - ls - 1);
+            t.right = a;
+            self.update(&mut t);
+            (Some(t), b)
+        }
+    }
+
+    /// キーが `key` 未満の要素とそれ以外に分割する。
+    fn split_key(&self, t: Link<K, M::Set, X::Set>, key: &K) -> LinkPair<K, M::Set, X::Set> {
+        let mut t = match t {
+            Some(t) => t,
+            None => return (None, None),
+        };
+        self.push(&mut t);
+        if t.key < *key {
+            let (a, b) = self.split_key(t.right.take(), key);
+            t.right = a;
+            self.update(&mut t);
+            (Some(t), b)
+        } else {
+            let (a, b) = self.split_key(t.left.take(), key);
+            t.left = b;
+            self.update(&mut t);
+            (a, Some(t))
+        }
+    }
+
+    fn new_node(&mut self, key: K, val: M::Set) -> Box<TreapNode<K, M::Set, X::Set>> {
+        Box::new(TreapNode {
+            key,
+            sum: val.clone(),
+            sum_rev: val.clone(),
+            val,
+            lazy: self.action.id(),
+            rev: false,
+            size: 1,
+            priority: self.next_priority(),
+            left: None,
+            right: None,
+        })
+    }
+
+    /// キー `key`、値 `val` の要素を挿入する。同じキーの要素が既にある場合はその前に挿入する。
+    pub fn insert(&mut self, key: K, val: M::Set) {
+        let node = self.new_node(key, val);
+        let root = self.root.take();
+        let (a, b) = self.split_key(root, &node.key);
+        let a = self.merge_link(a, Some(node));
+        self.root = self.merge_link(a, b);
+    }
+
+    /// キーの順序に関係なく、要素を位置 `k` に挿入する。
+    pub(crate) fn insert_at(&mut self, k: usize, key: K, val: M::Set) {
+        assert!(k <= self.len());
+        let node = self.new_node(key, val);
+        let root = self.root.take();
+        let (a, b) = self.split_at(root, k);
+        let a = self.merge_link(a, Some(node));
+        self.root = self.merge_link(a, b);
+    }
+
+    /// 位置 `k` の要素を削除し、そのキーと値を返す。
+    pub(crate) fn remove_at(&mut self, k: usize) -> Option<(K, M::Set)> {
+        let root = self.root.take();
+        let (a, b) = self.split_at(root, k);
+        let (m, c) = self.split_at(b, 1);
+        self.root = self.merge_link(a, c);
+        m.map(|m| (m.key, m.val))
+    }
+
+    /// 位置 `k` の要素の値を `val` に変更する。
+    pub(crate) fn set_at(&mut self, k: usize, val: M::Set) {
+        assert!(k < self.len());
+        self.with_range(k..k + 1, |_, t| {
+            t.sum = val.clone();
+            t.sum_rev = val.clone();
+            t.val = val;
+        });
+    }
+
+    /// キーが `key` である要素を一つ削除する。削除できたかを返す。
+    pub fn erase(&mut self, key: &K) -> bool {
+        let root = self.root.take();
+        let (a, b) = self.split_key(root, key);
+        let (m, c) = self.split_at(b, 1);
+        let found = m.as_ref().map_or(false, |m| m.key == *key);
+        let a = if found { a } else { self.merge_link(a, m) };
+        self.root = self.merge_link(a, c);
+        found
+    }
+
+    /// 先頭から `k` 番目 (0-indexed) の要素のキーを返す。
+    pub fn nth(&self, k: usize) -> Option<&K> {
+        let mut t = &self.root;
+        let mut k = k;
+        let mut flip = false;
+        while let Some(n) = t {
+            flip ^= n.rev;
+            let (l, r) = n.children(flip);
+            let ls = Self::size(l);
+            if k < ls {
+                t = l;
+            } else if k == ls {
+                return Some(&n.key);
+            } else {
+                k -= ls + 1;
+                t = r;
+            }
+        }
+        None
+    }
+
+    /// 先頭から `k` 番目 (0-indexed) の要素の値を返す。
+    pub fn get(&self, k: usize) -> Option<M::Set> {
+        let mut t = &self.root;
+        let mut k = k;
+        let mut flip = false;
+        let mut f = self.action.id();
+        while let Some(n) = t {
+            let (l, r) = n.children(flip ^ n.rev);
+            let ls = Self::size(l);
+            if k == ls {
+                return Some(self.action.act(n.val.clone(), f));
+            }
+            flip ^= n.rev;
+            f = self.action.op(n.lazy.clone(), f);
+            if k < ls {
+                t = l;
+            } else {
+                k -= ls + 1;
+                t = r;
+            }
+        }
+        None
+    }
+
+    /// キーが `key` 未満の要素の個数を返す。
+    pub fn rank(&self, key: &K) -> usize {
+        let mut t = &self.root;
+        let mut cnt = 0;
+        let mut flip = false;
+        while let Some(n) = t {
+            flip ^= n.rev;
+            let (l, r) = n.children(flip);
+            if n.key < *key {
+                cnt += Self::size(l) + 1;
+                t = r;
+            } else {
+                t = l;
+            }
+        }
+        cnt
+    }
+
+    /// キーが `key` 未満の要素からなる Treap と、それ以外の要素からなる Treap に分割する。
+    pub fn split(mut self, key: &K) -> (Self, Self) {
+        let root = self.root.take();
+        let (a, b) = self.split_key(root, key);
+        let mut right = Self::with_seed(self.next_priority());
+        self.root = a;
+        right.root = b;
+        (self, right)
+    }
+
+    /// `other` の要素を全て末尾に連結する。
+    /// キーの昇順を保つには、`other` のキーが全て `self` のキー以上である必要がある。
+    pub fn merge(mut self, mut other: Self) -> Self {
+        let root = self.root.take();
+        self.root = self.merge_link(root, other.root.take());
+        self
+    }
+
+    /// 区間 `r` を取り出して `g` を適用し、元に戻す。
+    fn with_range<G>(&mut self, r: Range<usize>, g: G)
+    where
+        G: FnOnce(&Self, &mut TreapNode<K, M::Set, X::Set>),
+    {
+        let root = self.root.take();
+        let (a, b) = self.split_at(root, r.start);
+        let (mut b, c) = self.split_at(b, r.end - r.start);
+        if let Some(b) = b.as_mut() {
+            g(self, b);
+        }
+        let a = self.merge_link(a, b);
+        self.root = self.merge_link(a, c);
+    }
+
+    /// 位置が半開区間 `r` に含まれる要素に `f` を作用させる。
+    pub fn apply(&mut self, r: Range<usize>, f: X::Set) {
+        self.with_range(r, |s, t| s.apply_act(t, f));
+    }
+
+    /// 位置が半開区間 `r` に含まれる要素の並びを反転する。
+    pub fn reverse(&mut self, r: Range<usize>) {
+        self.with_range(r, |_, t| Self::apply_rev(t));
+    }
+
+    /// 親から `flip` の反転と `f` の作用が未評価である部分木 `t` の、半開区間 `[l, r)` 上の積を計算する。
+    fn fold_rec(
+        &self,
+        t: &Link<K, M::Set, X::Set>,
+        l: usize,
+        r: usize,
+        flip: bool,
+        f: X::Set,
+    ) -> M::Set {
+        let t = match t {
+            Some(t) if l < r => t,
+            _ => return self.monoid.id(),
+        };
+        if l == 0 && r >= t.size {
+            let s = if flip { &t.sum_rev } else { &t.sum };
+            return self.action.act(s.clone(), f);
+        }
+        let flip_c = flip ^ t.rev;
+        let f_c = self.action.op(t.lazy.clone(), f.clone());
+        let (a, b) = t.children(flip_c);
+        let ls = Self::size(a);
+        let mut res = self.fold_rec(a, l, r.min(ls), flip_c, f_c.clone());
+        if l <= ls && ls < r {
+            let v = self.action.act(t.val.clone(), f);
+            res = self.monoid.op(res, v);
+        }
+        if r > ls + 1 {
+            let v = self.fold_rec(b, l.max(ls + 1) - ls - 1, r - ls - 1, flip_c, f_c);
+            res = self.monoid.op(res, v);
+        }
+        res
+    }
+}
+
+#[snippet("data_structure/treap")]
+impl<K, M, X> Fold for Treap<K, M, X>
+where
+    K: Ord,
+    M: Monoid,
+    M::Set: Clone,
+    X: MonoidAction<M>,
+    X::Set: Clone,
+{
+    type Output = M::Set;
+
+    /// 位置が半開区間 `r` に含まれる要素の値の積を計算する。
+    fn fold(&self, r: Range<usize>) -> M::Set {
+        self.fold_rec(&self.root, r.start, r.end, false, self.action.id())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algebra::act_affine::ActAffine;
+    use crate::algebra::act_trivial::ActTrivial;
+    use crate::algebra::op_add::OpAdd;
+    use crate::algebra::op_min::OpMin;
+    use crate::algebra::op_sized_add::OpSizedAdd;
+    use crate::algebra::structure::{Associative, Identity, Magma};
+    use crate::utils::binary_search::BinarySearch;
+    use crate::utils::test_rng::XorShift;
+
+    #[test]
+    fn test_treap_ordered_set() {
+        let mut rng = XorShift::default();
+        let mut treap = Treap::<i64, OpMin<i64>, OpAdd<i64>>::new();
+        let mut naive: Vec<i64> = Vec::new();
+        for _ in 0..2000 {
+            let x = (rng.next() % 50) as i64;
+            match rng.next() % 3 {
+                0 | 1 => {
+                    treap.insert(x, x * 10);
+                    let i = naive.search_sorted_first(&x);
+                    naive.insert(i, x);
+                }
+                _ => {
+                    let i = naive.search_sorted_first(&x);
+                    let found = i < naive.len() && naive[i] == x;
+                    if found {
+                        naive.remove(i);
+                    }
+                    assert_eq!(treap.erase(&x), found);
+                }
+            }
+            assert_eq!(treap.len(), naive.len());
+            assert_eq!(treap.rank(&x), naive.search_sorted_first(&x));
+            let k = rng.next() as usize % (naive.len() + 1);
+            assert_eq!(treap.nth(k), naive.get(k));
+            let l = treap.rank(&(x / 2));
+            let r = treap.rank(&x);
+            let min = naive[l..r].iter().map(|&y| y * 10).min();
+            assert_eq!(treap.fold(l..r), min.unwrap_or(std::i64::MAX));
+        }
+    }
+
+    #[test]
+    fn test_treap_split_merge() {
+        let mut treap = Treap::<i32, OpSizedAdd<i64>, ActTrivial>::new();
+        for &x in &[5, 1, 8, 3, 9, 2, 7] {
+            treap.insert(x, (x as i64, 1));
+        }
+        let (a, b) = treap.split(&5);
+        assert_eq!(a.len(), 3);
+        assert_eq!(b.len(), 4);
+        assert_eq!(a.fold(0..3), (6, 3));
+        assert_eq!(b.fold(0..4), (29, 4));
+        assert_eq!(b.nth(0), Some(&5));
+        let c = b.merge(a);
+        let keys: Vec<_> = (0..c.len()).map(|k| *c.nth(k).unwrap()).collect();
+        assert_eq!(keys, vec![5, 7, 8, 9, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_treap_reverse_non_commutative() {
+        // 非可換な演算 (アフィン変換の合成) で反転を確かめる
+        #[derive(Default)]
+        struct OpCompose;
+
+        impl Magma for OpCompose {
+            type Set = (i64, i64);
+
+            // x を適用した後に y を適用する
+            fn op(&self, x: (i64, i64), y: (i64, i64)) -> (i64, i64) {
+                (x.0 * y.0 % 998244353, (x.1 * y.0 + y.1) % 998244353)
+            }
+        }
+
+        impl Associative for OpCompose {}
+
+        impl Identity for OpCompose {
+            fn id(&self) -> (i64, i64) {
+                (1, 0)
+            }
+        }
+
+        let op = OpCompose;
+        let mut rng = XorShift::default();
+        let n = 30;
+        let mut treap = Treap::<usize, OpCompose, ActTrivial>::new();
+        let mut naive = Vec::new();
+        for i in 0..n {
+            let v = ((rng.next() % 10 + 1) as i64, (rng.next() % 10) as i64);
+            treap.insert(i, v);
+            naive.push(v);
+        }
+        for _ in 0..1000 {
+            let l = rng.next() as usize % (n + 1);
+            let r = rng.next() as usize % (n + 1);
+            let (l, r) = (l.min(r), l.max(r));
+            if rng.next() % 2 == 0 {
+                treap.reverse(l..r);
+                naive[l..r].reverse();
+            } else {
+                let expected = naive[l..r].iter().fold(op.id(), |s, &v| op.op(s, v));
+                assert_eq!(treap.fold(l..r), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_treap_range_affine_reverse() {
+        let mut rng = XorShift::default();
+        let n = 30;
+        let mut treap = Treap::<usize, OpSizedAdd<i64>, ActAffine<i64>>::new();
+        let mut naive = Vec::new();
+        for i in 0..n {
+            let v = (rng.next() % 10) as i64;
+            treap.insert(i, (v, 1));
+            naive.push(v);
+        }
+        for _ in 0..1000 {
+            let l = rng.next() as usize % (n + 1);
+            let r = rng.next() as usize % (n + 1);
+            let (l, r) = (l.min(r), l.max(r));
+            match rng.next() % 3 {
+                0 => {
+                    treap.reverse(l..r);
+                    naive[l..r].reverse();
+                }
+                1 => {
+                    let (a, b) = ((rng.next() % 3) as i64 - 1, (rng.next() % 10) as i64);
+                    treap.apply(l..r, (a, b));
+                    naive[l..r].iter_mut().for_each(|v| *v = a * *v + b);
+                }
+                _ => {
+                    let s = naive[l..r].iter().sum::<i64>();
+                    assert_eq!(treap.fold(l..r), (s, r - l));
+                }
+            }
+            let k = rng.next() as usize % n;
+            assert_eq!(treap.get(k), Some((naive[k], 1)));
+        }
+    }
+}
@@ -1,12 +1,15 @@
 //! Union-Find木。
 use cargo_snippet::snippet;
 
+use super::super::algebra::structure::Monoid;
+
 /// Union-Find木。
 #[snippet("data_structure/union_find")]
 #[derive(Debug, Clone)]
 pub struct UnionFind {
     par: Vec<usize>,
     size: Vec<usize>,
+    count: usize,
 }
 
 #[snippet("data_structure/union_find")]
@@ -16,6 +19,7 @@ impl UnionFind {
         Self {
             par: vec![0; n],
             size: vec![1; n],
+            count: n,
         }
     }
 
@@ -41,6 +45,7 @@ impl UnionFind {
         self.size[x] += self.size[y];
         self.size[y] = 0;
         self.par[y] = x;
+        self.count -= 1;
         x
     }
 
@@ -54,11 +59,115 @@ impl UnionFind {
         let x = self.find_root(a);
         self.size[x]
     }
+
+    /// 連結成分の個数を返す。
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// 連結成分ごとに頂点を列挙する。連結成分は最小の頂点の昇順に並ぶ。
+    pub fn groups(&mut self) -> Vec<Vec<usize>> {
+        let n = self.par.len();
+        let mut index = vec![None; n];
+        let mut groups = Vec::with_capacity(self.count);
+        for v in 0..n {
+            let x = self.find_root(v);
+            let i = *index[x].get_or_insert_with(|| {
+                groups.push(Vec::with_capacity(self.size[x]));
+                groups.len() - 1
+            });
+            groups[i].push(v);
+        }
+        groups
+    }
+}
+
+/// 連結成分ごとに値を持つ Union-Find木。
+///
+/// 連結成分の値は `union` の際にモノイドの演算で併合される。
+#[snippet("data_structure/union_find")]
+pub struct UnionFindWithValue<M: Monoid> {
+    uf: UnionFind,
+    values: Vec<M::Set>,
+    monoid: M,
+}
+
+#[snippet("data_structure/union_find")]
+impl<M> UnionFindWithValue<M>
+where
+    M: Monoid,
+    M::Set: Clone,
+{
+    /// 大きさ `n` の Union-Find 木を、各頂点の値を単位元として初期化する。
+    pub fn new(n: usize) -> Self {
+        let monoid = M::default();
+        Self::from(vec![monoid.id(); n])
+    }
+
+    /// 頂点 `a` の属する連結成分の代表元を返す。
+    pub fn find_root(&mut self, a: usize) -> usize {
+        self.uf.find_root(a)
+    }
+
+    /// 辺 `(a, b)` を追加し、追加後の連結成分の代表元を返す。
+    /// 連結成分の値は `op(a の連結成分の値, b の連結成分の値)` となる。
+    pub fn union(&mut self, a: usize, b: usize) -> usize {
+        let x = self.uf.find_root(a);
+        let y = self.uf.find_root(b);
+        if x == y {
+            return x;
+        }
+        let val = self
+            .monoid
+            .op(self.values[x].clone(), self.values[y].clone());
+        let z = self.uf.union(x, y);
+        self.values[z] = val;
+        z
+    }
+
+    /// 頂点 `a` と 頂点 `b` が同じ連結成分に属しているかを返す。
+    pub fn in_same_set(&mut self, a: usize, b: usize) -> bool {
+        self.uf.in_same_set(a, b)
+    }
+
+    /// 頂点 `a` の属する連結成分のサイズを返す。
+    pub fn group_size(&mut self, a: usize) -> usize {
+        self.uf.group_size(a)
+    }
+
+    /// 頂点 `a` の属する連結成分の値を返す。
+    pub fn value(&mut self, a: usize) -> &M::Set {
+        let x = self.uf.find_root(a);
+        &self.values[x]
+    }
+
+    /// 連結成分の個数を返す。
+    pub fn count(&self) -> usize {
+        self.uf.count()
+    }
+
+    /// 連結成分ごとに頂点を列挙する。連結成分は最小の頂点の昇順に並ぶ。
+    pub fn groups(&mut self) -> Vec<Vec<usize>> {
+        self.uf.groups()
+    }
+}
+
+#[snippet("data_structure/union_find")]
+impl<M: Monoid> From<Vec<M::Set>> for UnionFindWithValue<M> {
+    fn from(v: Vec<M::Set>) -> Self {
+        Self {
+            uf: UnionFind::new(v.len()),
+            values: v,
+            monoid: M::default(),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::algebra::op_add::OpAdd;
+    use crate::algebra::op_min::OpMin;
 
     #[test]
     fn test_union_find_0() {
@@ -85,4 +194,37 @@ mod tests {
         assert_eq!(uf.group_size(4), 3);
         assert_eq!(uf.group_size(5), 1);
     }
+
+    #[test]
+    fn test_union_find_groups() {
+        let mut uf = UnionFind::new(6);
+        assert_eq!(uf.count(), 6);
+        uf.union(3, 4);
+        uf.union(5, 1);
+        uf.union(4, 0);
+        uf.union(0, 3);
+        assert_eq!(uf.count(), 3);
+        assert_eq!(uf.groups(), vec![vec![0, 3, 4], vec![1, 5], vec![2]]);
+    }
+
+    #[test]
+    fn test_union_find_with_value() {
+        let mut uf = UnionFindWithValue::<OpAdd<i64>>::from(vec![1, 2, 3, 4, 5]);
+        uf.union(0, 1);
+        uf.union(3, 4);
+        assert_eq!(*uf.value(1), 3);
+        assert_eq!(*uf.value(2), 3);
+        assert_eq!(*uf.value(4), 9);
+        uf.union(1, 4);
+        uf.union(0, 3);
+        assert_eq!(*uf.value(0), 12);
+        assert_eq!(uf.count(), 2);
+        assert_eq!(uf.groups(), vec![vec![0, 1, 3, 4], vec![2]]);
+
+        let mut uf = UnionFindWithValue::<OpMin<i32>>::new(3);
+        assert_eq!(*uf.value(0), std::i32::MAX);
+        uf.union(0, 2);
+        assert!(uf.in_same_set(2, 0));
+        assert_eq!(uf.group_size(0), 2);
+    }
 }
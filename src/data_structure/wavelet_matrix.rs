@@ -0,0 +1,437 @@
+//! ウェーブレット行列。
+use cargo_snippet::snippet;
+
+#[snippet("data_structure/wavelet_matrix")]
+use std::cmp::Reverse;
+#[snippet("data_structure/wavelet_matrix")]
+use std::collections::BinaryHeap;
+#[snippet("data_structure/wavelet_matrix")]
+use std::ops::Range;
+
+use super::super::algebra::op_add::OpAdd;
+use super::bit_vector::BitVector;
+use super::fenwick::FenwickTree;
+use super::traits::Fold;
+
+/// ウェーブレット行列
+///
+/// 非負整数列に対して、
+/// - `i` 番目の値の取得
+/// - 区間内の値 `x` の個数・`k` 番目の値 `x` の位置の取得
+/// - 区間内で `k` 番目に小さい値の取得
+/// - 区間内で値が指定した範囲に含まれる要素の個数の取得
+/// - 区間内で指定した値未満の最大値・以上の最小値の取得
+/// - 区間内で出現回数の多い値の上位 `k` 個の取得
+///
+/// を値のビット長を `B` として `O(B)` (位置の取得は `O(B log N)`、上位 `k` 個の取得は `O(k B log(k B))`) で行うことができる。
+#[snippet("data_structure/wavelet_matrix")]
+#[derive(Debug, Clone)]
+pub struct WaveletMatrix {
+    len: usize,
+    log: usize,
+    // mats[d] は上から d 番目のビットを表す
+    mats: Vec<BitVector>,
+    // 各段で 0 となる要素の個数
+    mid: Vec<usize>,
+}
+
+#[snippet("data_structure/wavelet_matrix")]
+impl WaveletMatrix {
+    /// 列の長さを返す。
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// 列が空かどうかを返す。
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// 上から `d` 段目が表すビットの位置を返す。
+    fn bit(&self, d: usize) -> usize {
+        self.log - 1 - d
+    }
+
+    /// `d` 段目の位置 `i` に対応する、`d + 1` 段目での位置を返す。
+    fn next_pos(&self, d: usize, i: usize, b: bool) -> usize {
+        if b {
+            self.mid[d] + self.mats[d].rank1(i)
+        } else {
+            self.mats[d].rank0(i)
+        }
+    }
+
+    /// 全ての値が `x` 未満であるかを返す。
+    fn exceeds(&self, x: u64) -> bool {
+        self.log < 64 && x >> self.log > 0
+    }
+
+    /// `i` 番目の値を返す。
+    pub fn access(&self, i: usize) -> u64 {
+        assert!(i < self.len);
+        let mut i = i;
+        let mut res = 0;
+        for d in 0..self.log {
+            let b = self.mats[d].get(i);
+            if b {
+                res |= 1 << self.bit(d);
+            }
+            i = self.next_pos(d, i, b);
+        }
+        res
+    }
+
+    /// 区間 `[0, r)` に含まれる値 `x` の個数を返す。
+    pub fn rank(&self, x: u64, r: usize) -> usize {
+        let leq = match x.checked_add(1) {
+            Some(y) => self.count_less(0..r, y),
+            None => r,
+        };
+        leq - self.count_less(0..r, x)
+    }
+
+    /// `k` 番目 (0-indexed) の値 `x` の位置を返す。
+    pub fn select(&self, x: u64, k: usize) -> Option<usize> {
+        if self.exceeds(x) || self.rank(x, self.len) <= k {
+            return None;
+        }
+        // 最下段での値 x の開始位置
+        let mut p = 0;
+        for d in 0..self.log {
+            p = self.next_pos(d, p, x >> self.bit(d) & 1 == 1);
+        }
+        p += k;
+        for d in (0..self.log).rev() {
+            p = if x >> self.bit(d) & 1 == 1 {
+                self.mats[d].select1(p - self.mid[d]).unwrap()
+            } else {
+                self.mats[d].select0(p).unwrap()
+            };
+        }
+        Some(p)
+    }
+
+    /// 区間 `r` 内で `k` 番目 (0-indexed) に小さい値を返す。
+    pub fn quantile(&self, r: Range<usize>, k: usize) -> u64 {
+        assert!(k < r.end - r.start);
+        let (mut l, mut r, mut k) = (r.start, r.end, k);
+        let mut res = 0;
+        for d in 0..self.log {
+            let (l0, r0) = (self.mats[d].rank0(l), self.mats[d].rank0(r));
+            if k < r0 - l0 {
+                l = l0;
+                r = r0;
+            } else {
+                k -= r0 - l0;
+                res |= 1 << self.bit(d);
+                l = self.next_pos(d, l, true);
+                r = self.next_pos(d, r, true);
+            }
+        }
+        res
+    }
+
+    /// 区間 `r` 内で `x` 未満の値の個数を返す。
+    fn count_less(&self, r: Range<usize>, x: u64) -> usize {
+        if self.exceeds(x) {
+            return r.end - r.start;
+        }
+        let (mut l, mut r) = (r.start, r.end);
+        let mut res = 0;
+        for d in 0..self.log {
+            let b = x >> self.bit(d) & 1 == 1;
+            if b {
+                res += self.mats[d].rank0(r) - self.mats[d].rank0(l);
+            }
+            l = self.next_pos(d, l, b);
+            r = self.next_pos(d, r, b);
+        }
+        res
+    }
+
+    /// 区間 `r` 内で値が半開区間 `vals` に含まれるものの個数を返す。
+    pub fn range_freq(&self, r: Range<usize>, vals: Range<u64>) -> usize {
+        if vals.start >= vals.end {
+            return 0;
+        }
+        self.count_less(r.clone(), vals.end) - self.count_less(r, vals.start)
+    }
+
+    /// 区間 `r` 内で `upper` 未満の最大の値を返す。
+    pub fn prev_value(&self, r: Range<usize>, upper: u64) -> Option<u64> {
+        let cnt = self.count_less(r.clone(), upper);
+        if cnt == 0 {
+            None
+        } else {
+            Some(self.quantile(r, cnt - 1))
+        }
+    }
+
+    /// 区間 `r` 内で `lower` 以上の最小の値を返す。
+    pub fn next_value(&self, r: Range<usize>, lower: u64) -> Option<u64> {
+        let cnt = self.count_less(r.clone(), lower);
+        if cnt == r.end - r.start {
+            None
+        } else {
+            Some(self.quantile(r, cnt))
+        }
+    }
+
+    /// 区間 `r` 内で出現回数の多い値を、出現回数の降順 (同じ場合は値の昇順) に最大 `k` 個、
+    /// `(値, 出現回数)` の組として返す。
+    pub fn top_k(&self, r: Range<usize>, k: usize) -> Vec<(u64, usize)> {
+        // (区間の幅, 確定した上位ビット, 段, 区間の左端) を幅の大きい順に取り出す
+        let mut heap = BinaryHeap::new();
+        if r.start < r.end {
+            heap.push((r.end - r.start, Reverse(0), 0, r.start));
+        }
+        let mut res = vec![];
+        while res.len() < k {
+            let (width, Reverse(x), d, l) = match heap.pop() {
+                Some(e) => e,
+                None => break,
+            };
+            if d == self.log {
+                res.push((x, width));
+                continue;
+            }
+            let r = l + width;
+            let (l0, r0) = (self.next_pos(d, l, false), self.next_pos(d, r, false));
+            if l0 < r0 {
+                heap.push((r0 - l0, Reverse(x), d + 1, l0));
+            }
+            let (l1, r1) = (self.next_pos(d, l, true), self.next_pos(d, r, true));
+            if l1 < r1 {
+                heap.push((r1 - l1, Reverse(x | 1 << self.bit(d)), d + 1, l1));
+            }
+        }
+        res
+    }
+}
+
+#[snippet("data_structure/wavelet_matrix")]
+impl From<Vec<u64>> for WaveletMatrix {
+    fn from(v: Vec<u64>) -> Self {
+        let len = v.len();
+        let max = v.iter().copied().max().unwrap_or(0);
+        let log = (64 - max.leading_zeros() as usize).max(1);
+        let mut mats = Vec::with_capacity(log);
+        let mut mid = Vec::with_capacity(log);
+        let mut cur = v;
+        for d in 0..log {
+            let bit = log - 1 - d;
+            let bits: Vec<bool> = cur.iter().map(|&x| x >> bit & 1 == 1).collect();
+            let (zeros, ones): (Vec<u64>, Vec<u64>) = cur.iter().partition(|&&x| x >> bit & 1 == 0);
+            mid.push(zeros.len());
+            mats.push(BitVector::from(bits));
+            cur = zeros;
+            cur.extend(ones);
+        }
+        Self {
+            len,
+            log,
+            mats,
+            mid,
+        }
+    }
+}
+
+/// 重み付きウェーブレット行列
+///
+/// ウェーブレット行列の各段にフェニック木を持たせ、各要素に重みを持たせたデータ構造。
+/// - 重みの加算
+/// - 区間内で値が指定した範囲に含まれる要素の重みの和の取得
+///
+/// を値のビット長を `B` として `O(B log N)` で行うことができる。
+#[snippet("data_structure/wavelet_matrix")]
+pub struct WeightedWaveletMatrix {
+    wm: WaveletMatrix,
+    // fen[0] は元の並び、fen[d + 1] は d 段目で並べ替えた後の並びで重みを持つ
+    fen: Vec<FenwickTree<OpAdd<i64>>>,
+}
+
+#[snippet("data_structure/wavelet_matrix")]
+impl WeightedWaveletMatrix {
+    /// 列の長さを返す。
+    pub fn len(&self) -> usize {
+        self.wm.len()
+    }
+
+    /// 列が空かどうかを返す。
+    pub fn is_empty(&self) -> bool {
+        self.wm.is_empty()
+    }
+
+    /// 重みを持たないウェーブレット行列への参照を返す。
+    pub fn matrix(&self) -> &WaveletMatrix {
+        &self.wm
+    }
+
+    /// `i` 番目の要素の重みに `w` を加算する。
+    pub fn add(&mut self, i: usize, w: i64) {
+        let mut i = i;
+        self.fen[0].add(i, w);
+        for d in 0..self.wm.log {
+            i = self.wm.next_pos(d, i, self.wm.mats[d].get(i));
+            self.fen[d + 1].add(i, w);
+        }
+    }
+
+    /// 区間 `r` 内で `x` 未満の値を持つ要素の重みの和を返す。
+    fn sum_less(&self, r: Range<usize>, x: u64) -> i64 {
+        if self.wm.exceeds(x) {
+            return self.fen[0].fold(r);
+        }
+        let (mut l, mut r) = (r.start, r.end);
+        let mut res = 0;
+        for d in 0..self.wm.log {
+            let b = x >> self.wm.bit(d) & 1 == 1;
+            if b {
+                let (l0, r0) = (self.wm.mats[d].rank0(l), self.wm.mats[d].rank0(r));
+                res += self.fen[d + 1].fold(l0..r0);
+            }
+            l = self.wm.next_pos(d, l, b);
+            r = self.wm.next_pos(d, r, b);
+        }
+        res
+    }
+
+    /// 区間 `r` 内で値が半開区間 `vals` に含まれる要素の重みの和を返す。
+    pub fn range_sum(&self, r: Range<usize>, vals: Range<u64>) -> i64 {
+        if vals.start >= vals.end {
+            return 0;
+        }
+        self.sum_less(r.clone(), vals.end) - self.sum_less(r, vals.start)
+    }
+}
+
+#[snippet("data_structure/wavelet_matrix")]
+impl From<Vec<(u64, i64)>> for WeightedWaveletMatrix {
+    /// `(値, 重み)` の列から作成する。
+    fn from(v: Vec<(u64, i64)>) -> Self {
+        let wm = WaveletMatrix::from(v.iter().map(|&(x, _)| x).collect::<Vec<_>>());
+        let fen = (0..=wm.log).map(|_| FenwickTree::new(wm.len())).collect();
+        let mut res = Self { wm, fen };
+        for (i, &(_, w)) in v.iter().enumerate() {
+            res.add(i, w);
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_rng::XorShift;
+
+    #[test]
+    fn test_wavelet_matrix() {
+        let mut rng = XorShift::default();
+        for &(n, m) in &[
+            (0, 1),
+            (1, 1),
+            (30, 1),
+            (50, 8),
+            (100, 1000),
+            (40, std::u64::MAX),
+        ] {
+            let v: Vec<u64> = (0..n)
+                .map(|_| {
+                    if m == std::u64::MAX {
+                        rng.next()
+                    } else {
+                        rng.next() % m
+                    }
+                })
+                .collect();
+            let wm = WaveletMatrix::from(v.clone());
+            assert_eq!(wm.len(), n);
+            for (i, &x) in v.iter().enumerate() {
+                assert_eq!(wm.access(i), x);
+                let k = v[..i].iter().filter(|&&y| y == x).count();
+                assert_eq!(wm.rank(x, i), k);
+                assert_eq!(wm.select(x, k), Some(i));
+            }
+            for _ in 0..300 {
+                let l = rng.next() as usize % (n + 1);
+                let r = rng.next() as usize % (n + 1);
+                let (l, r) = (l.min(r), l.max(r));
+                let mut s = v[l..r].to_vec();
+                s.sort_unstable();
+                for (k, &x) in s.iter().enumerate() {
+                    assert_eq!(wm.quantile(l..r, k), x);
+                }
+                let (a, b) = if m == std::u64::MAX {
+                    (rng.next(), rng.next())
+                } else {
+                    (rng.next() % (m + 2), rng.next() % (m + 2))
+                };
+                let (a, b) = (a.min(b), a.max(b));
+                let cnt = s.iter().filter(|&&x| a <= x && x < b).count();
+                assert_eq!(wm.range_freq(l..r, a..b), cnt);
+                assert_eq!(
+                    wm.prev_value(l..r, b),
+                    s.iter().rev().find(|&&x| x < b).copied()
+                );
+                assert_eq!(wm.next_value(l..r, a), s.iter().find(|&&x| x >= a).copied());
+                let mut freq: Vec<(u64, usize)> = vec![];
+                for &x in &s {
+                    match freq.last_mut() {
+                        Some((y, c)) if *y == x => *c += 1,
+                        _ => freq.push((x, 1)),
+                    }
+                }
+                freq.sort_by_key(|&(x, c)| (Reverse(c), x));
+                let k = rng.next() as usize % (freq.len() + 2);
+                freq.truncate(k);
+                assert_eq!(wm.top_k(l..r, k), freq);
+            }
+        }
+    }
+
+    #[test]
+    fn test_wavelet_matrix_top_k() {
+        let wm = WaveletMatrix::from(vec![3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5]);
+        assert_eq!(wm.top_k(0..11, 3), vec![(5, 3), (1, 2), (3, 2)]);
+        assert_eq!(wm.top_k(2..6, 10), vec![(1, 1), (4, 1), (5, 1), (9, 1)]);
+        assert_eq!(wm.top_k(3..3, 1), vec![]);
+    }
+
+    #[test]
+    fn test_wavelet_matrix_select_missing() {
+        let wm = WaveletMatrix::from(vec![3, 1, 3, 2]);
+        assert_eq!(wm.select(3, 1), Some(2));
+        assert_eq!(wm.select(3, 2), None);
+        assert_eq!(wm.select(0, 0), None);
+        assert_eq!(wm.select(100, 0), None);
+    }
+
+    #[test]
+    fn test_weighted_wavelet_matrix() {
+        let mut rng = XorShift::default();
+        let n = 50;
+        let mut v: Vec<(u64, i64)> = (0..n)
+            .map(|_| (rng.next() % 20, (rng.next() % 100) as i64 - 50))
+            .collect();
+        let mut wm = WeightedWaveletMatrix::from(v.clone());
+        for _ in 0..500 {
+            let i = rng.next() as usize % n;
+            let w = (rng.next() % 100) as i64 - 50;
+            wm.add(i, w);
+            v[i].1 += w;
+            let l = rng.next() as usize % (n + 1);
+            let r = rng.next() as usize % (n + 1);
+            let (l, r) = (l.min(r), l.max(r));
+            let a = rng.next() % 25;
+            let b = rng.next() % 25;
+            let (a, b) = (a.min(b), a.max(b));
+            let s: i64 = v[l..r]
+                .iter()
+                .filter(|&&(x, _)| a <= x && x < b)
+                .map(|&(_, w)| w)
+                .sum();
+            assert_eq!(wm.range_sum(l..r, a..b), s);
+        }
+        assert_eq!(wm.matrix().access(3), v[3].0);
+    }
+}
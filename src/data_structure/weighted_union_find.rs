@@ -0,0 +1,184 @@
+//! 重み付き Union-Find木。
+use cargo_snippet::snippet;
+
+use super::super::algebra::structure::Group;
+
+/// 重み付き Union-Find木。
+///
+/// 各頂点 `v` にポテンシャル `x_v` (群の元) を持たせ、
+/// `op(recip(x_a), x_b) = w` (加法群であれば `x_b - x_a = w`) という制約を管理する。
+#[snippet("data_structure/weighted_union_find")]
+pub struct WeightedUnionFind<G: Group> {
+    par: Vec<usize>,
+    size: Vec<usize>,
+    // 親から見たポテンシャルの差分
+    weight: Vec<G::Set>,
+    group: G,
+}
+
+#[snippet("data_structure/weighted_union_find")]
+impl<G> WeightedUnionFind<G>
+where
+    G: Group,
+    G::Set: Clone,
+{
+    /// 大きさ `n` の重み付き Union-Find 木を初期化する。
+    pub fn new(n: usize) -> Self {
+        let group = G::default();
+        Self {
+            par: vec![0; n],
+            size: vec![1; n],
+            weight: vec![group.id(); n],
+            group,
+        }
+    }
+
+    /// 頂点 `a` の属する連結成分の代表元を返す。
+    pub fn find_root(&mut self, a: usize) -> usize {
+        if self.size[a] > 0 {
+            return a;
+        }
+        let p = self.par[a];
+        let root = self.find_root(p);
+        self.weight[a] = self
+            .group
+            .op(self.weight[p].clone(), self.weight[a].clone());
+        self.par[a] = root;
+        root
+    }
+
+    /// 代表元から見た頂点 `a` のポテンシャルを返す。
+    fn potential(&mut self, a: usize) -> G::Set {
+        self.find_root(a);
+        self.weight[a].clone()
+    }
+
+    /// `op(recip(x_a), x_b) = w` という制約を追加し、追加後の連結成分の代表元を返す。
+    /// 既存の制約と矛盾する場合は `Err` を返す。
+    pub fn union_with(&mut self, a: usize, b: usize, w: G::Set) -> Result<usize, &'static str> {
+        let x = self.find_root(a);
+        let y = self.find_root(b);
+        let pa = self.potential(a);
+        let pb = self.potential(b);
+        if x == y {
+            let d = self.group.op(self.group.recip(pa), pb);
+            return if d == w { Ok(x) } else { Err("Contradiction") };
+        }
+        // 代表元 x から見た代表元 y のポテンシャル
+        let d = self.group.op(self.group.op(pa, w), self.group.recip(pb));
+        let (x, y, d) = if self.size[x] < self.size[y] {
+            (y, x, self.group.recip(d))
+        } else {
+            (x, y, d)
+        };
+        self.size[x] += self.size[y];
+        self.size[y] = 0;
+        self.par[y] = x;
+        self.weight[y] = d;
+        Ok(x)
+    }
+
+    /// 頂点 `a` と 頂点 `b` が同じ連結成分に属している場合、`op(recip(x_a), x_b)` を返す。
+    pub fn diff(&mut self, a: usize, b: usize) -> Option<G::Set> {
+        if self.find_root(a) != self.find_root(b) {
+            return None;
+        }
+        let pa = self.potential(a);
+        let pb = self.potential(b);
+        Some(self.group.op(self.group.recip(pa), pb))
+    }
+
+    /// 頂点 `a` と 頂点 `b` が同じ連結成分に属しているかを返す。
+    pub fn in_same_set(&mut self, a: usize, b: usize) -> bool {
+        self.find_root(a) == self.find_root(b)
+    }
+
+    /// 頂点 `a` の属する連結成分のサイズを返す。
+    pub fn group_size(&mut self, a: usize) -> usize {
+        let x = self.find_root(a);
+        self.size[x]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algebra::op_add::OpAdd;
+    use crate::algebra::op_xor::OpXor;
+    use crate::algebra::structure::{Associative, Identity, Magma, Recip};
+
+    #[test]
+    fn test_weighted_union_find_add() {
+        let mut uf = WeightedUnionFind::<OpAdd<i64>>::new(5);
+        assert_eq!(uf.diff(0, 1), None);
+        assert!(uf.union_with(0, 1, 3).is_ok());
+        assert!(uf.union_with(2, 3, -2).is_ok());
+        assert_eq!(uf.diff(0, 1), Some(3));
+        assert_eq!(uf.diff(1, 0), Some(-3));
+        assert_eq!(uf.diff(1, 2), None);
+        assert!(uf.union_with(1, 3, 10).is_ok());
+        assert_eq!(uf.diff(0, 2), Some(15));
+        assert_eq!(uf.diff(3, 0), Some(-13));
+        assert!(uf.union_with(0, 2, 15).is_ok());
+        assert!(uf.union_with(0, 2, 14).is_err());
+        assert_eq!(uf.group_size(2), 4);
+        assert_eq!(uf.group_size(4), 1);
+    }
+
+    #[test]
+    fn test_weighted_union_find_xor() {
+        let mut uf = WeightedUnionFind::<OpXor<u32>>::new(4);
+        assert!(uf.union_with(0, 1, 5).is_ok());
+        assert!(uf.union_with(1, 2, 3).is_ok());
+        assert_eq!(uf.diff(0, 2), Some(6));
+        assert!(uf.union_with(2, 0, 6).is_ok());
+        assert!(uf.union_with(2, 0, 7).is_err());
+        assert!(!uf.in_same_set(0, 3));
+    }
+
+    #[test]
+    fn test_weighted_union_find_permutation() {
+        // 3 要素の置換群 (非可換)
+        #[derive(Default)]
+        struct OpPerm;
+
+        impl Magma for OpPerm {
+            type Set = [usize; 3];
+
+            // x を適用した後に y を適用する
+            fn op(&self, x: [usize; 3], y: [usize; 3]) -> [usize; 3] {
+                [y[x[0]], y[x[1]], y[x[2]]]
+            }
+        }
+
+        impl Associative for OpPerm {}
+
+        impl Identity for OpPerm {
+            fn id(&self) -> [usize; 3] {
+                [0, 1, 2]
+            }
+        }
+
+        impl Recip for OpPerm {
+            fn recip(&self, x: [usize; 3]) -> [usize; 3] {
+                let mut y = [0; 3];
+                for (i, &v) in x.iter().enumerate() {
+                    y[v] = i;
+                }
+                y
+            }
+        }
+
+        let op = OpPerm;
+        let s = [1, 0, 2];
+        let t = [0, 2, 1];
+        let mut uf = WeightedUnionFind::<OpPerm>::new(3);
+        assert!(uf.union_with(0, 1, s).is_ok());
+        assert!(uf.union_with(2, 1, t).is_ok());
+        let d = op.op(s, op.recip(t));
+        assert_eq!(uf.diff(0, 2), Some(d));
+        assert_eq!(uf.diff(2, 0), Some(op.recip(d)));
+        assert!(uf.union_with(0, 2, d).is_ok());
+        assert!(uf.union_with(0, 2, op.op(t, s)).is_err());
+    }
+}
@@ -1,3 +1,5 @@
 //! 幾何に関連するモジュール。
+pub mod convex_hull_trick;
+pub mod li_chao_tree;
 pub mod line;
 pub mod point;
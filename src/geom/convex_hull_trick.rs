@@ -0,0 +1,184 @@
+//! Convex Hull Trick。
+use cargo_snippet::snippet;
+
+#[snippet("geom/convex_hull_trick")]
+use std::collections::VecDeque;
+
+/// 傾きが単調な直線の追加に対する Convex Hull Trick
+///
+/// - 直線 `y = ax + b` の追加 (償却 `O(1)`)
+/// - x 座標 `x` における y 座標の最小値 (最大値) の取得 (`O(log N)`)
+/// - 単調増加する x 座標に対する同様のクエリ (償却 `O(1)`)
+///
+/// を行うことができる。
+/// 直線は、最小値を求める場合は傾きの降順に、最大値を求める場合は傾きの昇順に追加する必要がある。
+#[snippet("geom/convex_hull_trick")]
+#[derive(Debug, Clone)]
+pub struct ConvexHullTrick<T> {
+    lines: VecDeque<(T, T)>,
+    is_max: bool,
+}
+
+#[snippet("geom/convex_hull_trick")]
+macro_rules! convex_hull_trick_impl {
+    ($($t:ty)*) => ($(
+        impl ConvexHullTrick<$t> {
+            /// 最小値を求める Convex Hull Trick を作成する。
+            pub fn new_min() -> Self {
+                Self {
+                    lines: VecDeque::new(),
+                    is_max: false,
+                }
+            }
+
+            /// 最大値を求める Convex Hull Trick を作成する。
+            pub fn new_max() -> Self {
+                Self {
+                    lines: VecDeque::new(),
+                    is_max: true,
+                }
+            }
+
+            /// 追加された直線がないかを返す。
+            pub fn is_empty(&self) -> bool {
+                self.lines.is_empty()
+            }
+
+            /// 直線 `l1`, `l3` の間に直線 `l2` を置く必要がないかを返す。
+            fn is_redundant(l1: ($t, $t), l2: ($t, $t), l3: ($t, $t)) -> bool {
+                let (a1, b1) = (l1.0 as i128, l1.1 as i128);
+                let (a2, b2) = (l2.0 as i128, l2.1 as i128);
+                let (a3, b3) = (l3.0 as i128, l3.1 as i128);
+                (b2 - b1) * (a2 - a3) >= (b3 - b2) * (a1 - a2)
+            }
+
+            fn eval(line: ($t, $t), x: $t) -> $t {
+                line.0 * x + line.1
+            }
+
+            /// 直線 `y = ax + b` を追加する。
+            pub fn add_line(&mut self, a: $t, b: $t) {
+                let line = if self.is_max {
+                    // 符号を反転して最小値を求める問題に帰着する
+                    assert!(
+                        a > <$t>::min_value() && b > <$t>::min_value(),
+                        "values must be greater than MIN"
+                    );
+                    (-a, -b)
+                } else {
+                    (a, b)
+                };
+                if let Some(&last) = self.lines.back() {
+                    assert!(last.0 >= line.0, "slopes must be monotone");
+                    if last.0 == line.0 {
+                        if last.1 <= line.1 {
+                            return;
+                        }
+                        self.lines.pop_back();
+                    }
+                }
+                while self.lines.len() >= 2 {
+                    let n = self.lines.len();
+                    if Self::is_redundant(self.lines[n - 2], self.lines[n - 1], line) {
+                        self.lines.pop_back();
+                    } else {
+                        break;
+                    }
+                }
+                self.lines.push_back(line);
+            }
+
+            fn output(&self, y: $t) -> $t {
+                if self.is_max {
+                    -y
+                } else {
+                    y
+                }
+            }
+
+            /// x 座標 `x` における y 座標の最小値 (最大値) を返す。
+            pub fn query(&self, x: $t) -> Option<$t> {
+                if self.lines.is_empty() {
+                    return None;
+                }
+                // f_i(x) <= f_{i+1}(x) となる最小の i
+                let (mut lo, mut hi) = (0, self.lines.len() - 1);
+                while lo < hi {
+                    let mid = (lo + hi) / 2;
+                    if Self::eval(self.lines[mid], x) <= Self::eval(self.lines[mid + 1], x) {
+                        hi = mid;
+                    } else {
+                        lo = mid + 1;
+                    }
+                }
+                Some(self.output(Self::eval(self.lines[lo], x)))
+            }
+
+            /// x 座標 `x` における y 座標の最小値 (最大値) を返す。
+            /// `x` は呼び出しごとに単調増加する必要がある。
+            pub fn query_monotone(&mut self, x: $t) -> Option<$t> {
+                while self.lines.len() >= 2
+                    && Self::eval(self.lines[0], x) >= Self::eval(self.lines[1], x)
+                {
+                    self.lines.pop_front();
+                }
+                let y = Self::eval(*self.lines.front()?, x);
+                Some(self.output(y))
+            }
+        }
+    )*)
+}
+
+// is_redundant で積を i128 で計算するため、i128 は対象としない
+#[snippet("geom/convex_hull_trick")]
+convex_hull_trick_impl! { isize i8 i16 i32 i64 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_rng::XorShift;
+
+    #[test]
+    fn test_convex_hull_trick() {
+        let mut rng = XorShift::default();
+        for &is_max in &[false, true] {
+            let mut lines: Vec<(i64, i64)> = (0..50)
+                .map(|_| {
+                    let a = (rng.next() % 41) as i64 - 20;
+                    let b = (rng.next() % 201) as i64 - 100;
+                    (a, b)
+                })
+                .collect();
+            lines.sort_unstable_by_key(|&(a, _)| if is_max { a } else { -a });
+            let mut cht = if is_max {
+                ConvexHullTrick::<i64>::new_max()
+            } else {
+                ConvexHullTrick::<i64>::new_min()
+            };
+            let mut monotone = cht.clone();
+            assert!(cht.is_empty());
+            assert_eq!(cht.query(0), None);
+            for (i, &(a, b)) in lines.iter().enumerate() {
+                cht.add_line(a, b);
+                monotone.add_line(a, b);
+                for x in -30..=30 {
+                    let ys = lines[..=i].iter().map(|&(a, b)| a * x + b);
+                    let expected = if is_max { ys.max() } else { ys.min() };
+                    assert_eq!(cht.query(x), expected);
+                }
+            }
+            for x in -30..=30 {
+                let ys = lines.iter().map(|&(a, b)| a * x + b);
+                let expected = if is_max { ys.max() } else { ys.min() };
+                assert_eq!(monotone.query_monotone(x), expected);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "values must be greater than MIN")]
+    fn test_convex_hull_trick_max_min_value() {
+        let mut cht = ConvexHullTrick::<i64>::new_max();
+        cht.add_line(std::i64::MIN, 0);
+    }
+}
@@ -0,0 +1,358 @@
+//! Li Chao Tree。
+use cargo_snippet::snippet;
+
+#[snippet("geom/li_chao_tree")]
+use std::ops::Range;
+
+use super::super::utils::binary_search::BinarySearch;
+
+/// Li Chao Tree
+///
+/// あらかじめ与えた x 座標の集合に対して、
+/// - 直線 `y = ax + b` (あるいは x 座標が区間に含まれる部分のみの線分) の追加
+/// - 与えた x 座標における、追加した直線の y 座標の最小値 (最大値) の取得
+///
+/// を `O(log N)` (線分の追加は `O(log^2 N)`) で行うことができる。
+#[snippet("geom/li_chao_tree")]
+#[derive(Debug, Clone)]
+pub struct LiChaoTree<T> {
+    xs: Vec<T>,
+    lines: Vec<Option<(T, T)>>,
+    is_max: bool,
+}
+
+/// 動的 Li Chao Tree
+///
+/// x 座標の範囲 `[lo, hi)` に対して、必要なノードのみを作成する Li Chao Tree。
+/// 各操作を範囲の幅を `W` として `O(log W)` (線分の追加は `O(log^2 W)`) で行うことができる。
+#[snippet("geom/li_chao_tree")]
+#[derive(Debug, Clone)]
+pub struct DynamicLiChaoTree<T> {
+    lo: T,
+    hi: T,
+    lines: Vec<Option<(T, T)>>,
+    children: Vec<[Option<usize>; 2]>,
+    root: Option<usize>,
+    is_max: bool,
+}
+
+#[snippet("geom/li_chao_tree")]
+macro_rules! li_chao_tree_impl {
+    ($($t:ty)*) => ($(
+        impl LiChaoTree<$t> {
+            fn with_mode(xs: Vec<$t>, is_max: bool) -> Self {
+                let mut xs = xs;
+                xs.sort_unstable();
+                xs.dedup();
+                let n = xs.len();
+                Self {
+                    xs,
+                    lines: vec![None; 4 * n.max(1)],
+                    is_max,
+                }
+            }
+
+            /// x 座標の集合 `xs` に対して、最小値を求める Li Chao Tree を作成する。
+            pub fn new_min(xs: Vec<$t>) -> Self {
+                Self::with_mode(xs, false)
+            }
+
+            /// x 座標の集合 `xs` に対して、最大値を求める Li Chao Tree を作成する。
+            pub fn new_max(xs: Vec<$t>) -> Self {
+                Self::with_mode(xs, true)
+            }
+
+            fn add_rec(&mut self, k: usize, lo: usize, hi: usize, line: ($t, $t)) {
+                let cur = match self.lines[k] {
+                    Some(cur) => cur,
+                    None => {
+                        self.lines[k] = Some(line);
+                        return;
+                    }
+                };
+                let f = |(a, b): ($t, $t), x: $t| a * x + b;
+                let m = lo + (hi - lo) / 2;
+                let (xl, xm) = (self.xs[lo], self.xs[m]);
+                let left_better = f(line, xl) < f(cur, xl);
+                let mid_better = f(line, xm) < f(cur, xm);
+                let (keep, rest) = if mid_better { (line, cur) } else { (cur, line) };
+                self.lines[k] = Some(keep);
+                if hi - lo == 1 {
+                    return;
+                }
+                if left_better != mid_better {
+                    self.add_rec(k << 1, lo, m, rest);
+                } else {
+                    self.add_rec((k << 1) + 1, m, hi, rest);
+                }
+            }
+
+            fn add_segment_rec(
+                &mut self,
+                k: usize,
+                lo: usize,
+                hi: usize,
+                r: &Range<usize>,
+                line: ($t, $t),
+            ) {
+                if hi <= r.start || r.end <= lo {
+                    return;
+                }
+                if r.start <= lo && hi <= r.end {
+                    self.add_rec(k, lo, hi, line);
+                    return;
+                }
+                let m = lo + (hi - lo) / 2;
+                self.add_segment_rec(k << 1, lo, m, r, line);
+                self.add_segment_rec((k << 1) + 1, m, hi, r, line);
+            }
+
+            fn normalize(&self, a: $t, b: $t) -> ($t, $t) {
+                if self.is_max {
+                    (-a, -b)
+                } else {
+                    (a, b)
+                }
+            }
+
+            /// 直線 `y = ax + b` を追加する。
+            pub fn add_line(&mut self, a: $t, b: $t) {
+                let n = self.xs.len();
+                self.add_segment_rec(1, 0, n, &(0..n), self.normalize(a, b));
+            }
+
+            /// 直線 `y = ax + b` の x 座標が半開区間 `r` に含まれる部分を追加する。
+            pub fn add_segment(&mut self, a: $t, b: $t, r: Range<$t>) {
+                let l = self.xs.search_sorted_first(&r.start);
+                let r = self.xs.search_sorted_first(&r.end);
+                let n = self.xs.len();
+                self.add_segment_rec(1, 0, n, &(l..r), self.normalize(a, b));
+            }
+
+            /// x 座標 `x` における y 座標の最小値 (最大値) を返す。
+            /// `x` を通る直線がない場合は `None` を返す。
+            pub fn query(&self, x: $t) -> Option<$t> {
+                let i = self.xs.binary_search(&x).expect("unknown x coordinate");
+                let (mut k, mut lo, mut hi) = (1, 0, self.xs.len());
+                let mut res: Option<$t> = None;
+                loop {
+                    if let Some((a, b)) = self.lines[k] {
+                        let y = a * x + b;
+                        res = Some(res.map_or(y, |r| r.min(y)));
+                    }
+                    if hi - lo == 1 {
+                        break;
+                    }
+                    let m = lo + (hi - lo) / 2;
+                    if i < m {
+                        k <<= 1;
+                        hi = m;
+                    } else {
+                        k = (k << 1) + 1;
+                        lo = m;
+                    }
+                }
+                if self.is_max {
+                    res.map(|y| -y)
+                } else {
+                    res
+                }
+            }
+        }
+
+        impl DynamicLiChaoTree<$t> {
+            fn with_mode(r: Range<$t>, is_max: bool) -> Self {
+                assert!(r.start < r.end);
+                Self {
+                    lo: r.start,
+                    hi: r.end,
+                    lines: Vec::new(),
+                    children: Vec::new(),
+                    root: None,
+                    is_max,
+                }
+            }
+
+            /// x 座標の範囲 `r` に対して、最小値を求める Li Chao Tree を作成する。
+            pub fn new_min(r: Range<$t>) -> Self {
+                Self::with_mode(r, false)
+            }
+
+            /// x 座標の範囲 `r` に対して、最大値を求める Li Chao Tree を作成する。
+            pub fn new_max(r: Range<$t>) -> Self {
+                Self::with_mode(r, true)
+            }
+
+            fn new_node(&mut self) -> usize {
+                self.lines.push(None);
+                self.children.push([None, None]);
+                self.lines.len() - 1
+            }
+
+            fn add_rec(&mut self, k: Option<usize>, lo: $t, hi: $t, line: ($t, $t)) -> usize {
+                let k = k.unwrap_or_else(|| self.new_node());
+                let cur = match self.lines[k] {
+                    Some(cur) => cur,
+                    None => {
+                        self.lines[k] = Some(line);
+                        return k;
+                    }
+                };
+                let f = |(a, b): ($t, $t), x: $t| a * x + b;
+                let m = lo + (hi - lo) / 2;
+                let left_better = f(line, lo) < f(cur, lo);
+                let mid_better = f(line, m) < f(cur, m);
+                let (keep, rest) = if mid_better { (line, cur) } else { (cur, line) };
+                self.lines[k] = Some(keep);
+                if hi - lo == 1 {
+                    return k;
+                }
+                let (c, lo, hi) = if left_better != mid_better {
+                    (0, lo, m)
+                } else {
+                    (1, m, hi)
+                };
+                let child = self.add_rec(self.children[k][c], lo, hi, rest);
+                self.children[k][c] = Some(child);
+                k
+            }
+
+            fn add_segment_rec(
+                &mut self,
+                k: Option<usize>,
+                lo: $t,
+                hi: $t,
+                r: &Range<$t>,
+                line: ($t, $t),
+            ) -> Option<usize> {
+                if hi <= r.start || r.end <= lo {
+                    return k;
+                }
+                if r.start <= lo && hi <= r.end {
+                    return Some(self.add_rec(k, lo, hi, line));
+                }
+                let k = k.unwrap_or_else(|| self.new_node());
+                let m = lo + (hi - lo) / 2;
+                let left = self.add_segment_rec(self.children[k][0], lo, m, r, line);
+                self.children[k][0] = left;
+                let right = self.add_segment_rec(self.children[k][1], m, hi, r, line);
+                self.children[k][1] = right;
+                Some(k)
+            }
+
+            fn normalize(&self, a: $t, b: $t) -> ($t, $t) {
+                if self.is_max {
+                    (-a, -b)
+                } else {
+                    (a, b)
+                }
+            }
+
+            /// 直線 `y = ax + b` を追加する。
+            pub fn add_line(&mut self, a: $t, b: $t) {
+                let line = self.normalize(a, b);
+                self.root = Some(self.add_rec(self.root, self.lo, self.hi, line));
+            }
+
+            /// 直線 `y = ax + b` の x 座標が半開区間 `r` に含まれる部分を追加する。
+            pub fn add_segment(&mut self, a: $t, b: $t, r: Range<$t>) {
+                let line = self.normalize(a, b);
+                self.root = self.add_segment_rec(self.root, self.lo, self.hi, &r, line);
+            }
+
+            /// x 座標 `x` における y 座標の最小値 (最大値) を返す。
+            /// `x` を通る直線がない場合は `None` を返す。
+            pub fn query(&self, x: $t) -> Option<$t> {
+                assert!(self.lo <= x && x < self.hi);
+                let (mut k, mut lo, mut hi) = (self.root, self.lo, self.hi);
+                let mut res: Option<$t> = None;
+                while let Some(node) = k {
+                    if let Some((a, b)) = self.lines[node] {
+                        let y = a * x + b;
+                        res = Some(res.map_or(y, |r| r.min(y)));
+                    }
+                    let m = lo + (hi - lo) / 2;
+                    if x < m {
+                        k = self.children[node][0];
+                        hi = m;
+                    } else {
+                        k = self.children[node][1];
+                        lo = m;
+                    }
+                }
+                if self.is_max {
+                    res.map(|y| -y)
+                } else {
+                    res
+                }
+            }
+        }
+    )*)
+}
+
+#[snippet("geom/li_chao_tree")]
+li_chao_tree_impl! { isize i8 i16 i32 i64 i128 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_rng::XorShift;
+
+    #[test]
+    fn test_li_chao_tree() {
+        let mut rng = XorShift::default();
+        let xs: Vec<i64> = (0..30).map(|_| (rng.next() % 100) as i64 - 50).collect();
+        for &is_max in &[false, true] {
+            let mut tree = if is_max {
+                LiChaoTree::<i64>::new_max(xs.clone())
+            } else {
+                LiChaoTree::<i64>::new_min(xs.clone())
+            };
+            let mut dynamic = if is_max {
+                DynamicLiChaoTree::<i64>::new_max(-50..50)
+            } else {
+                DynamicLiChaoTree::<i64>::new_min(-50..50)
+            };
+            // (a, b, 区間)
+            let mut segments = Vec::new();
+            for _ in 0..100 {
+                let a = (rng.next() % 21) as i64 - 10;
+                let b = (rng.next() % 201) as i64 - 100;
+                if rng.next() % 2 == 0 {
+                    tree.add_line(a, b);
+                    dynamic.add_line(a, b);
+                    segments.push((a, b, -50..50));
+                } else {
+                    let l = (rng.next() % 101) as i64 - 50;
+                    let r = (rng.next() % 101) as i64 - 50;
+                    let (l, r) = (l.min(r), l.max(r));
+                    tree.add_segment(a, b, l..r);
+                    dynamic.add_segment(a, b, l..r);
+                    segments.push((a, b, l..r));
+                }
+                for x in -50..50 {
+                    let ys = segments
+                        .iter()
+                        .filter(|(_, _, r)| r.contains(&x))
+                        .map(|(a, b, _)| a * x + b);
+                    let expected = if is_max { ys.max() } else { ys.min() };
+                    if xs.contains(&x) {
+                        assert_eq!(tree.query(x), expected);
+                    }
+                    assert_eq!(dynamic.query(x), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_li_chao_tree_i32() {
+        let mut tree = LiChaoTree::<i32>::new_min(vec![0, 1, 2, 3]);
+        assert_eq!(tree.query(0), None);
+        tree.add_line(1, 0);
+        tree.add_line(-1, 3);
+        assert_eq!(tree.query(0), Some(0));
+        assert_eq!(tree.query(2), Some(1));
+        assert_eq!(tree.query(3), Some(0));
+    }
+}
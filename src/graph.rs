@@ -1,6 +1,15 @@
 //! グラフに関連するモジュール。
+pub mod bellman_ford;
+pub mod bfs;
 pub mod closed_path;
 pub mod dijkstra;
+pub mod dinic;
+pub mod floyd_warshall;
 pub mod ford_fulkerson;
+pub mod grid;
+pub mod johnson;
 pub mod scc;
+pub mod static_graph;
+#[cfg(test)]
+pub(crate) mod test_graph;
 pub mod two_sat;
@@ -0,0 +1,216 @@
+//! Bellman-Ford 法および SPFA による単一始点最短経路。
+//!
+//! 辺の重みは `dijkstra` と同様にモノイド `M` で表すが、単位元より小さい重み (負の辺) を許す。
+use std::collections::VecDeque;
+
+use super::static_graph::Neighbors;
+use crate::algebra::structure::Monoid;
+
+/// 始点の集合 `sources` からの最短距離を Bellman-Ford 法で `O(NM)` で求める。
+///
+/// 到達できない頂点の距離は `None` となる。
+/// 始点から到達できる負閉路がある場合は、負閉路から到達できる頂点 (始点からの最短距離が負の無限大となる頂点) を昇順に並べて `Err` で返す。
+pub fn bellman_ford<M, G>(graph: &G, sources: &[usize]) -> Result<Vec<Option<M::Set>>, Vec<usize>>
+where
+    M: Monoid,
+    M::Set: Ord + Copy,
+    G: Neighbors<Weight = M::Set>,
+{
+    let monoid = M::default();
+    let n = graph.node_count();
+    let mut dist = vec![None; n];
+    for &s in sources {
+        dist[s] = Some(monoid.id());
+    }
+    let mut negative = vec![false; n];
+    // 始点からの最短経路として採用している経路の辺の数
+    let mut len = vec![0; n];
+    // 最初の n 回で負閉路を検出し、残りの n 回で負閉路の影響を全体に伝播させる
+    for _ in 0..2 * n {
+        let mut updated = false;
+        for v in 0..n {
+            let d = match dist[v] {
+                Some(d) => d,
+                None => continue,
+            };
+            for &(u, w) in graph.neighbors(v) {
+                if negative[u] {
+                    continue;
+                }
+                if negative[v] {
+                    // 距離が負の無限大となる頂点からは、距離を更新せずに印だけを伝播させる
+                    dist[u].get_or_insert(d);
+                    negative[u] = true;
+                    updated = true;
+                    continue;
+                }
+                let next = monoid.op(d, w);
+                if dist[u].map_or(true, |du| next < du) {
+                    dist[u] = Some(next);
+                    len[u] = len[v] + 1;
+                    // n 本以上の辺を使う経路が最短となるのは負閉路がある場合に限る
+                    if len[u] >= n {
+                        negative[u] = true;
+                    }
+                    updated = true;
+                }
+            }
+        }
+        if !updated {
+            break;
+        }
+    }
+    if negative.iter().any(|&f| f) {
+        Err((0..n).filter(|&v| negative[v]).collect())
+    } else {
+        Ok(dist)
+    }
+}
+
+/// 始点の集合 `sources` からの最短距離を SPFA (Shortest Path Faster Algorithm) で求める。
+///
+/// 最悪計算量は `O(NM)` だが、多くの場合は Bellman-Ford 法より高速に動作する。
+/// 返り値は `bellman_ford` と同じで、負閉路を検出した場合は `bellman_ford` で影響を受ける頂点を求める。
+pub fn spfa<M, G>(graph: &G, sources: &[usize]) -> Result<Vec<Option<M::Set>>, Vec<usize>>
+where
+    M: Monoid,
+    M::Set: Ord + Copy,
+    G: Neighbors<Weight = M::Set>,
+{
+    let monoid = M::default();
+    let n = graph.node_count();
+    let mut dist = vec![None; n];
+    let mut in_queue = vec![false; n];
+    // 始点からの最短経路として採用している経路の辺の数
+    let mut len = vec![0; n];
+    let mut queue = VecDeque::new();
+    for &s in sources {
+        if dist[s].is_none() {
+            dist[s] = Some(monoid.id());
+            in_queue[s] = true;
+            queue.push_back(s);
+        }
+    }
+    while let Some(v) = queue.pop_front() {
+        in_queue[v] = false;
+        let d = dist[v].unwrap();
+        for &(u, w) in graph.neighbors(v) {
+            let next = monoid.op(d, w);
+            if dist[u].map_or(true, |du| next < du) {
+                dist[u] = Some(next);
+                len[u] = len[v] + 1;
+                if len[u] >= n {
+                    // n 本以上の辺を使う経路が最短となるのは負閉路がある場合に限る
+                    return bellman_ford::<M, G>(graph, sources);
+                }
+                if !in_queue[u] {
+                    in_queue[u] = true;
+                    queue.push_back(u);
+                }
+            }
+        }
+    }
+    Ok(dist)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algebra::op_add::OpAdd;
+    use crate::graph::static_graph::{GraphBuilder, StaticGraph};
+    use crate::graph::test_graph::random_potential_edges;
+    use crate::utils::test_rng::XorShift;
+
+    // 負閉路を持たない場合の最短距離を、全ての辺の緩和を N 回繰り返して求める
+    fn naive(n: usize, edges: &[(usize, usize, i64)], start: usize) -> Vec<Option<i64>> {
+        let mut dist = vec![None; n];
+        dist[start] = Some(0);
+        for _ in 0..n {
+            for &(from, to, w) in edges {
+                if let Some(d) = dist[from] {
+                    if dist[to].map_or(true, |dt| d + w < dt) {
+                        dist[to] = Some(d + w);
+                    }
+                }
+            }
+        }
+        dist
+    }
+
+    #[test]
+    fn test_bellman_ford() {
+        let mut builder = GraphBuilder::directed(5);
+        builder
+            .add_weighted_edge(0, 1, 4i64)
+            .add_weighted_edge(0, 2, 2)
+            .add_weighted_edge(2, 1, -3)
+            .add_weighted_edge(1, 3, 1)
+            .add_weighted_edge(3, 2, 5);
+        let graph = builder.build();
+        let expected = Ok(vec![Some(0), Some(-1), Some(2), Some(0), None]);
+        assert_eq!(bellman_ford::<OpAdd<i64>, _>(&graph, &[0]), expected);
+        assert_eq!(spfa::<OpAdd<i64>, _>(&graph, &[0]), expected);
+    }
+
+    #[test]
+    fn test_bellman_ford_negative_cycle() {
+        // 1 -> 2 -> 3 -> 1 が負閉路で、4 はそこから到達でき、5 は到達できない
+        let mut builder = GraphBuilder::directed(7);
+        builder
+            .add_weighted_edge(0, 1, 1i64)
+            .add_weighted_edge(1, 2, 1)
+            .add_weighted_edge(2, 3, -3)
+            .add_weighted_edge(3, 1, 1)
+            .add_weighted_edge(3, 4, 1)
+            .add_weighted_edge(0, 5, 1)
+            .add_weighted_edge(6, 6, -1);
+        let graph = builder.build();
+        assert_eq!(
+            bellman_ford::<OpAdd<i64>, _>(&graph, &[0]),
+            Err(vec![1, 2, 3, 4])
+        );
+        assert_eq!(spfa::<OpAdd<i64>, _>(&graph, &[0]), Err(vec![1, 2, 3, 4]));
+        assert_eq!(
+            bellman_ford::<OpAdd<i64>, _>(&graph, &[5]),
+            Ok(vec![None, None, None, None, None, Some(0), None])
+        );
+        assert_eq!(
+            spfa::<OpAdd<i64>, _>(&graph, &[0, 6]),
+            Err(vec![1, 2, 3, 4, 6])
+        );
+    }
+
+    #[test]
+    fn test_bellman_ford_negative_cycle_overflow() {
+        // 負閉路上の頂点の距離を更新し続けてオーバーフローしないことを確かめる
+        let n = 2000;
+        let mut builder = GraphBuilder::directed(n);
+        for i in 0..n {
+            builder.add_weighted_edge(i, (i + 1) % n, -3_000_000_000_000i64);
+        }
+        let graph = builder.build();
+        let expected: Vec<_> = (0..n).collect();
+        assert_eq!(
+            bellman_ford::<OpAdd<i64>, _>(&graph, &[0]),
+            Err(expected.clone())
+        );
+        assert_eq!(spfa::<OpAdd<i64>, _>(&graph, &[0]), Err(expected));
+    }
+
+    #[test]
+    fn test_bellman_ford_random() {
+        let mut rng = XorShift::default();
+        for _ in 0..100 {
+            let (n, edges) = random_potential_edges(&mut rng);
+            let graph = StaticGraph::from_edges(n, &edges);
+            for start in 0..n {
+                let expected = naive(n, &edges, start);
+                assert_eq!(
+                    bellman_ford::<OpAdd<i64>, _>(&graph, &[start]),
+                    Ok(expected.clone())
+                );
+                assert_eq!(spfa::<OpAdd<i64>, _>(&graph, &[start]), Ok(expected));
+            }
+        }
+    }
+}
@@ -0,0 +1,231 @@
+//! 幅優先探索による最短経路。
+//!
+//! 辺の重みが全て等しいグラフ、重みが 0 または 1 のグラフ、重みが小さい非負整数のグラフの最短経路を、
+//! 二分ヒープを用いずに求める。
+use std::collections::VecDeque;
+
+use super::dijkstra::ShortestPathTree;
+use super::static_graph::Neighbors;
+
+/// 辺の重みを無視して、始点の集合 `sources` からの最短経路木 (距離は辺の数) を `O(N + M)` で求める。
+pub fn bfs<G: Neighbors>(graph: &G, sources: &[usize]) -> ShortestPathTree<usize> {
+    let n = graph.node_count();
+    let mut dist = vec![None; n];
+    let mut prev = vec![None; n];
+    let mut order = vec![];
+    let mut queue = VecDeque::new();
+    for &s in sources {
+        if dist[s].is_none() {
+            dist[s] = Some(0);
+            queue.push_back(s);
+        }
+    }
+    while let Some(v) = queue.pop_front() {
+        order.push(v);
+        let d = dist[v].unwrap();
+        for &(u, _) in graph.neighbors(v) {
+            if dist[u].is_none() {
+                dist[u] = Some(d + 1);
+                prev[u] = Some(v);
+                queue.push_back(u);
+            }
+        }
+    }
+    ShortestPathTree::new(dist, prev, order)
+}
+
+/// 辺の重みが 0 または 1 のグラフで、始点の集合 `sources` からの最短経路木を `O(N + M)` で求める。
+pub fn zero_one_bfs<G: Neighbors<Weight = usize>>(
+    graph: &G,
+    sources: &[usize],
+) -> ShortestPathTree<usize> {
+    let n = graph.node_count();
+    let mut dist = vec![None; n];
+    let mut prev = vec![None; n];
+    let mut done = vec![false; n];
+    let mut order = vec![];
+    let mut deque = VecDeque::new();
+    for &s in sources {
+        dist[s] = Some(0);
+        deque.push_back(s);
+    }
+    while let Some(v) = deque.pop_front() {
+        if done[v] {
+            continue;
+        }
+        done[v] = true;
+        order.push(v);
+        let d = dist[v].unwrap();
+        for &(u, w) in graph.neighbors(v) {
+            assert!(w <= 1, "edge weight must be 0 or 1");
+            let next = d + w;
+            if dist[u].map_or(true, |du| next < du) {
+                dist[u] = Some(next);
+                prev[u] = Some(v);
+                if w == 0 {
+                    deque.push_front(u);
+                } else {
+                    deque.push_back(u);
+                }
+            }
+        }
+    }
+    ShortestPathTree::new(dist, prev, order)
+}
+
+/// Dial のアルゴリズム
+///
+/// 辺の重みが小さい非負整数のグラフで、始点の集合 `sources` からの最短経路木を求める。
+/// 辺の重みの最大値を `C` として、距離ごとのバケットを `C + 1` 個循環させて用いることで、
+/// `O(N C + M)` で動作する。
+pub fn dial<G: Neighbors<Weight = usize>>(graph: &G, sources: &[usize]) -> ShortestPathTree<usize> {
+    let n = graph.node_count();
+    let c = (0..n)
+        .flat_map(|v| graph.neighbors(v).iter().map(|&(_, w)| w))
+        .max()
+        .unwrap_or(0);
+    let mut dist = vec![None; n];
+    let mut prev = vec![None; n];
+    let mut done = vec![false; n];
+    let mut order = vec![];
+    let mut buckets = vec![vec![]; c + 1];
+    // バケットに入っている要素の数
+    let mut rest = 0;
+    for &s in sources {
+        dist[s] = Some(0);
+        buckets[0].push(s);
+        rest += 1;
+    }
+    let mut d = 0;
+    while rest > 0 {
+        while let Some(v) = buckets[d % (c + 1)].pop() {
+            rest -= 1;
+            if done[v] || dist[v] != Some(d) {
+                continue;
+            }
+            done[v] = true;
+            order.push(v);
+            for &(u, w) in graph.neighbors(v) {
+                let next = d + w;
+                if dist[u].map_or(true, |du| next < du) {
+                    dist[u] = Some(next);
+                    prev[u] = Some(v);
+                    buckets[next % (c + 1)].push(u);
+                    rest += 1;
+                }
+            }
+        }
+        d += 1;
+    }
+    ShortestPathTree::new(dist, prev, order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::dijkstra::dijkstra;
+    use crate::graph::static_graph::{GraphBuilder, StaticGraph};
+    use crate::utils::test_rng::XorShift;
+    use std::ops::RangeInclusive;
+
+    fn random_graph(rng: &mut XorShift, weight: RangeInclusive<u64>) -> StaticGraph<usize> {
+        let n = rng.next() as usize % 10 + 1;
+        let m = rng.next() as usize % 30;
+        let edges: Vec<_> = (0..m)
+            .map(|_| {
+                let from = rng.next() as usize % n;
+                let to = rng.next() as usize % n;
+                let w = weight.start() + rng.next() % (weight.end() - weight.start() + 1);
+                (from, to, w as usize)
+            })
+            .collect();
+        StaticGraph::from_edges(n, &edges)
+    }
+
+    // 最短経路木の経路が実在し、その長さが距離と一致することを確かめる
+    fn check_paths(graph: &StaticGraph<usize>, tree: &ShortestPathTree<usize>) {
+        for v in 0..graph.node_count() {
+            let path = match tree.path_to(v) {
+                Some(path) => path,
+                None => {
+                    assert_eq!(tree.dist(v), None);
+                    continue;
+                }
+            };
+            let mut len = 0;
+            for e in path.windows(2) {
+                len += graph
+                    .neighbors(e[0])
+                    .iter()
+                    .filter(|&&(u, _)| u == e[1])
+                    .map(|&(_, w)| w)
+                    .min()
+                    .unwrap();
+            }
+            assert_eq!(Some(len), tree.dist(v));
+        }
+    }
+
+    #[test]
+    fn test_bfs() {
+        let mut builder = GraphBuilder::undirected(6);
+        builder
+            .add_edge(0, 1)
+            .add_edge(1, 2)
+            .add_edge(2, 3)
+            .add_edge(0, 4)
+            .add_edge(4, 3);
+        let tree = bfs(&builder.build(), &[0]);
+        assert_eq!(
+            tree.dists(),
+            &[Some(0), Some(1), Some(2), Some(2), Some(1), None]
+        );
+        assert_eq!(tree.path_to(3), Some(vec![0, 4, 3]));
+        assert_eq!(tree.path_to(5), None);
+
+        let tree = bfs(&builder.build(), &[2, 4]);
+        assert_eq!(
+            tree.dists(),
+            &[Some(1), Some(1), Some(0), Some(1), Some(0), None]
+        );
+    }
+
+    #[test]
+    fn test_bfs_random() {
+        let mut rng = XorShift::default();
+        for _ in 0..100 {
+            let graph = random_graph(&mut rng, 1..=1);
+            for start in 0..graph.node_count() {
+                let tree = bfs(&graph, &[start]);
+                assert_eq!(tree.dists(), &dijkstra(&graph, start)[..]);
+                check_paths(&graph, &tree);
+            }
+        }
+    }
+
+    #[test]
+    fn test_zero_one_bfs_random() {
+        let mut rng = XorShift::default();
+        for _ in 0..100 {
+            let graph = random_graph(&mut rng, 0..=1);
+            for start in 0..graph.node_count() {
+                let tree = zero_one_bfs(&graph, &[start]);
+                assert_eq!(tree.dists(), &dijkstra(&graph, start)[..]);
+                check_paths(&graph, &tree);
+            }
+        }
+    }
+
+    #[test]
+    fn test_dial_random() {
+        let mut rng = XorShift::default();
+        for _ in 0..100 {
+            let graph = random_graph(&mut rng, 0..=5);
+            for start in 0..graph.node_count() {
+                let tree = dial(&graph, &[start]);
+                assert_eq!(tree.dists(), &dijkstra(&graph, start)[..]);
+                check_paths(&graph, &tree);
+            }
+        }
+    }
+}
@@ -1,9 +1,11 @@
 // 閉路を検出する
 
+use super::static_graph::Neighbors;
+
 #[derive(Debug, Clone)]
 pub struct Graph {
     n: usize,
-    data: Vec<Vec<usize>>,
+    data: Vec<Vec<(usize, ())>>,
 }
 
 impl Graph {
@@ -15,59 +17,75 @@ impl Graph {
     }
 
     pub fn add_edge(&mut self, from: usize, to: usize) {
-        self.data[from].push(to);
-        self.data[to].push(from);
+        self.data[from].push((to, ()));
+        self.data[to].push((from, ()));
+    }
+
+    pub fn closed_path(&self, start: usize) -> Vec<usize> {
+        closed_path(self, start)
+    }
+}
+
+impl Neighbors for Graph {
+    type Weight = ();
+
+    fn node_count(&self) -> usize {
+        self.n
+    }
+
+    fn neighbors(&self, v: usize) -> &[(usize, ())] {
+        &self.data[v]
     }
+}
 
-    fn dfs(
-        &self,
-        used: &mut Vec<bool>,
-        par: &mut Vec<Option<usize>>,
-        from: Option<usize>,
-        to: usize,
-    ) -> Option<(usize, usize)> {
-        par[to] = from;
+fn dfs<G: Neighbors>(
+    graph: &G,
+    used: &mut Vec<bool>,
+    par: &mut Vec<Option<usize>>,
+    from: Option<usize>,
+    to: usize,
+) -> Option<(usize, usize)> {
+    par[to] = from;
 
-        if used[to] {
-            return Some((to, from.unwrap()));
+    if used[to] {
+        return Some((to, from.unwrap()));
+    }
+    used[to] = true;
+    for &(v, _) in graph.neighbors(to) {
+        if from == Some(v) {
+            continue;
         }
-        used[to] = true;
-        for v in &self.data[to] {
-            if from == Some(*v) {
-                continue;
-            }
-            let res = self.dfs(used, par, Some(to), *v);
-            if res.is_some() {
-                return res;
-            }
+        let res = dfs(graph, used, par, Some(to), v);
+        if res.is_some() {
+            return res;
         }
-        None
     }
+    None
+}
 
-    pub fn closed_path(&self, start: usize) -> Vec<usize> {
-        let mut used = vec![false; self.n];
-        let mut par = vec![None; self.n];
-        let res = self.dfs(&mut used, &mut par, None, start);
-        println!("{:?}", par);
-        match res {
-            Some((s, t)) => {
-                let mut v = t;
-                let mut nodes = vec![s];
-                while v != s {
-                    nodes.push(v);
-                    v = par[v].unwrap();
-                }
-                println!("{:?}", nodes);
-                nodes
+/// 無向グラフ (両向きの辺を持つグラフ) で `start` から到達できる閉路を一つ返す。
+pub fn closed_path<G: Neighbors>(graph: &G, start: usize) -> Vec<usize> {
+    let n = graph.node_count();
+    let mut used = vec![false; n];
+    let mut par = vec![None; n];
+    match dfs(graph, &mut used, &mut par, None, start) {
+        Some((s, t)) => {
+            let mut v = t;
+            let mut nodes = vec![s];
+            while v != s {
+                nodes.push(v);
+                v = par[v].unwrap();
             }
-            None => Vec::new(),
+            nodes
         }
+        None => Vec::new(),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::graph::static_graph::GraphBuilder;
 
     #[test]
     fn test_closed_path() {
@@ -80,4 +98,20 @@ mod tests {
         let path = graph.closed_path(0);
         assert_eq!(path, vec![0, 2, 1]);
     }
+
+    #[test]
+    fn test_closed_path_static_graph() {
+        let mut builder = GraphBuilder::undirected(5);
+        builder
+            .add_edge(0, 1)
+            .add_edge(1, 2)
+            .add_edge(0, 2)
+            .add_edge(0, 3)
+            .add_edge(1, 4);
+        assert_eq!(closed_path(&builder.build(), 0), vec![0, 2, 1]);
+
+        let mut builder = GraphBuilder::undirected(4);
+        builder.add_edge(0, 1).add_edge(1, 2).add_edge(1, 3);
+        assert_eq!(closed_path(&builder.build(), 0), vec![]);
+    }
 }
@@ -1,13 +1,24 @@
+//! Dijkstra 法による単一始点最短経路。
+//!
+//! 辺の重みは `Ord` かつ `Copy` な台集合を持つモノイド `M` で表し、
+//! 経路の長さを辺の重みの積 (`OpAdd<i64>` なら和) とする。
+//! 重みは全て単位元以上 (負の辺を持たない) であることを仮定する。
 use std::cmp::Ordering;
 use std::collections::BinaryHeap;
 
+use super::static_graph::Neighbors;
+use crate::algebra::op_add::OpAdd;
+use crate::algebra::op_mul::OpMul;
+use crate::algebra::structure::{Identity, Magma, Monoid};
+use crate::data_structure::radix_heap::RadixHeap;
+
 #[derive(Copy, Clone, Eq, PartialEq)]
-pub struct State {
-    cost: usize,
+struct State<T> {
+    cost: T,
     position: usize,
 }
 
-impl Ord for State {
+impl<T: Ord> Ord for State<T> {
     fn cmp(&self, other: &Self) -> Ordering {
         other
             .cost
@@ -16,22 +27,16 @@ impl Ord for State {
     }
 }
 
-impl PartialOrd for State {
+impl<T: Ord> PartialOrd for State<T> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct Edge {
-    node: usize,
-    cost: usize,
-}
-
 #[derive(Debug, Clone)]
 pub struct Graph {
     n: usize,
-    data: Vec<Vec<Edge>>,
+    data: Vec<Vec<(usize, usize)>>,
 }
 
 impl Graph {
@@ -43,46 +48,214 @@ impl Graph {
     }
 
     pub fn add_edge(&mut self, from: usize, to: usize, cost: usize) {
-        self.data[from].push(Edge { node: to, cost });
+        self.data[from].push((to, cost));
     }
 
     pub fn dijkstra(&self, start: usize) -> Vec<Option<usize>> {
-        let mut dist: Vec<_> = (0..self.n).map(|_| std::usize::MAX).collect();
-        let mut heap = BinaryHeap::new();
+        dijkstra(self, start)
+    }
+
+    /// 始点の集合 `sources` からの最短経路木を求める。
+    ///
+    /// `target` を指定した場合は、`target` までの距離が確定した時点で探索を打ち切る。
+    pub fn shortest_path_tree(
+        &self,
+        sources: &[usize],
+        target: Option<usize>,
+    ) -> ShortestPathTree<usize> {
+        shortest_path_tree::<OpAdd<usize>, _>(self, sources, target)
+    }
+
+    /// 優先度付きキューに Radix Heap を用いて、`start` からの最短距離を求める。
+    pub fn dijkstra_radix(&self, start: usize) -> Vec<Option<usize>> {
+        dijkstra_radix(self, start)
+    }
+}
+
+impl Neighbors for Graph {
+    type Weight = usize;
+
+    fn node_count(&self) -> usize {
+        self.n
+    }
 
-        dist[start] = 0;
+    fn neighbors(&self, v: usize) -> &[(usize, usize)] {
+        &self.data[v]
+    }
+}
+
+/// 最短経路木
+///
+/// 各頂点への最短距離と、最短経路上で一つ手前の頂点を持つ。
+#[derive(Debug, Clone)]
+pub struct ShortestPathTree<T> {
+    dist: Vec<Option<T>>,
+    prev: Vec<Option<usize>>,
+    // 距離が確定した順に並べた頂点
+    order: Vec<usize>,
+}
+
+impl<T: Copy> ShortestPathTree<T> {
+    pub(crate) fn new(dist: Vec<Option<T>>, prev: Vec<Option<usize>>, order: Vec<usize>) -> Self {
+        Self { dist, prev, order }
+    }
+
+    /// 頂点 `v` への最短距離を返す。到達できない場合は `None` を返す。
+    pub fn dist(&self, v: usize) -> Option<T> {
+        self.dist[v]
+    }
+
+    /// 全ての頂点への最短距離を返す。
+    pub fn dists(&self) -> &[Option<T>] {
+        &self.dist
+    }
+
+    /// 最短経路上で頂点 `v` の一つ手前の頂点を返す。`v` が始点か到達できない場合は `None` を返す。
+    pub fn prev(&self, v: usize) -> Option<usize> {
+        self.prev[v]
+    }
+
+    /// 始点のいずれかから `target` までの最短経路を、通る頂点の列として返す。
+    pub fn path_to(&self, target: usize) -> Option<Vec<usize>> {
+        self.dist[target]?;
+        let mut path = vec![target];
+        let mut v = target;
+        while let Some(u) = self.prev[v] {
+            path.push(u);
+            v = u;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// `start` からの最短距離を求める。到達できない頂点は `None` となる。
+pub fn dijkstra<G: Neighbors<Weight = usize>>(graph: &G, start: usize) -> Vec<Option<usize>> {
+    shortest_path_tree::<OpAdd<usize>, _>(graph, &[start], None).dist
+}
+
+/// 始点の集合 `sources` からの最短経路木を求める。
+///
+/// `target` を指定した場合は、`target` までの距離が確定した時点で探索を打ち切る。
+/// このとき、`target` 以外の頂点の距離は確定していない (最短距離以上の値である) ことがある。
+pub fn shortest_path_tree<M, G>(
+    graph: &G,
+    sources: &[usize],
+    target: Option<usize>,
+) -> ShortestPathTree<M::Set>
+where
+    M: Monoid,
+    M::Set: Ord + Copy,
+    G: Neighbors<Weight = M::Set>,
+{
+    let monoid = M::default();
+    let n = graph.node_count();
+    let mut dist = vec![None; n];
+    let mut prev = vec![None; n];
+    let mut done = vec![false; n];
+    let mut order = vec![];
+    let mut heap = BinaryHeap::new();
+
+    for &s in sources {
+        dist[s] = Some(monoid.id());
         heap.push(State {
-            cost: 0,
-            position: start,
+            cost: monoid.id(),
+            position: s,
         });
+    }
+
+    while let Some(State { cost, position }) = heap.pop() {
+        if done[position] {
+            continue;
+        }
+        done[position] = true;
+        order.push(position);
+        if target == Some(position) {
+            break;
+        }
 
-        while let Some(State { cost, position }) = heap.pop() {
-            if cost > dist[position] {
-                continue;
+        for &(node, edge_cost) in graph.neighbors(position) {
+            let next = monoid.op(cost, edge_cost);
+            if dist[node].map_or(true, |d| next < d) {
+                dist[node] = Some(next);
+                prev[node] = Some(position);
+                heap.push(State {
+                    cost: next,
+                    position: node,
+                });
             }
+        }
+    }
 
-            for edge in &self.data[position] {
-                let next = State {
-                    cost: cost + edge.cost,
-                    position: edge.node,
-                };
+    ShortestPathTree::new(dist, prev, order)
+}
 
-                if next.cost < dist[next.position] {
-                    heap.push(next);
-                    dist[next.position] = next.cost;
-                }
+/// 始点の集合 `sources` からの最短経路木と、各頂点への最短経路の数を求める。
+///
+/// 経路の数は `C` (`FiniteField` など) で数える。
+/// 重みが単位元の辺があると経路の数が正しく求まらないため、全ての辺の重みは単位元より大きいことを仮定する。
+pub fn count_shortest_paths<M, C, G>(
+    graph: &G,
+    sources: &[usize],
+) -> (ShortestPathTree<M::Set>, Vec<C>)
+where
+    M: Monoid,
+    M::Set: Ord + Copy,
+    C: Copy + Eq,
+    OpAdd<C>: Monoid<Set = C>,
+    OpMul<C>: Identity<Set = C>,
+    G: Neighbors<Weight = M::Set>,
+{
+    let monoid = M::default();
+    let add = OpAdd::<C>::default();
+    let tree = shortest_path_tree::<M, G>(graph, sources, None);
+    let mut count = vec![add.id(); graph.node_count()];
+    for &s in sources {
+        count[s] = OpMul::<C>::default().id();
+    }
+    // 距離が確定した順に見ると、最短経路上の辺の始点は終点より先に現れる
+    for &v in &tree.order {
+        let d = tree.dist[v].unwrap();
+        for &(u, w) in graph.neighbors(v) {
+            if tree.dist[u] == Some(monoid.op(d, w)) {
+                count[u] = add.op(count[u], count[v]);
             }
         }
+    }
+    (tree, count)
+}
+
+/// 優先度付きキューに Radix Heap を用いて、`start` からの最短距離を求める。
+pub fn dijkstra_radix<G: Neighbors<Weight = usize>>(graph: &G, start: usize) -> Vec<Option<usize>> {
+    let mut dist = vec![None; graph.node_count()];
+    let mut heap = RadixHeap::<usize, usize>::new();
+
+    dist[start] = Some(0);
+    heap.push(0, start);
+
+    while let Some((cost, position)) = heap.pop() {
+        if dist[position].map_or(false, |d| cost > d) {
+            continue;
+        }
 
-        dist.into_iter()
-            .map(|x| if x == std::usize::MAX { None } else { Some(x) })
-            .collect::<Vec<_>>()
+        for &(node, edge_cost) in graph.neighbors(position) {
+            let next = cost + edge_cost;
+            if dist[node].map_or(true, |d| next < d) {
+                heap.push(next, node);
+                dist[node] = Some(next);
+            }
+        }
     }
+
+    dist
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::algebra::structure::Associative;
+    use crate::graph::static_graph::GraphBuilder;
+    use crate::math::mod_int::{FiniteField, Modulo};
 
     #[test]
     fn test_dijkstra() {
@@ -100,5 +273,157 @@ mod tests {
         assert_eq!(res, vec![Some(0), Some(1), Some(10), Some(3), Some(5)]);
         let res = graph.dijkstra(4);
         assert_eq!(res, vec![None, None, None, None, Some(0)]);
+        for start in 0..5 {
+            assert_eq!(graph.dijkstra_radix(start), graph.dijkstra(start));
+        }
+    }
+
+    #[test]
+    fn test_dijkstra_static_graph() {
+        let edges = [
+            (0, 2, 10),
+            (0, 1, 1),
+            (1, 3, 2),
+            (2, 1, 1),
+            (2, 3, 3),
+            (2, 4, 1),
+            (3, 0, 7),
+            (3, 4, 2),
+        ];
+        let mut builder = GraphBuilder::directed(5);
+        let mut graph = Graph::new(5);
+        for &(from, to, cost) in &edges {
+            builder.add_weighted_edge(from, to, cost);
+            graph.add_edge(from, to, cost);
+        }
+        let csr = builder.build();
+        for start in 0..5 {
+            assert_eq!(dijkstra(&csr, start), graph.dijkstra(start));
+            assert_eq!(dijkstra_radix(&csr, start), graph.dijkstra(start));
+        }
+    }
+
+    // 距離がオーバーフローする場合は、誤った距離を返さずに (デバッグビルドでは) パニックする
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn test_dijkstra_overflow() {
+        let mut graph = Graph::new(3);
+        graph.add_edge(0, 1, std::usize::MAX);
+        graph.add_edge(1, 2, 1);
+        graph.dijkstra(0);
+    }
+
+    #[test]
+    fn test_shortest_path_tree() {
+        let mut graph = Graph::new(6);
+        graph.add_edge(0, 2, 10);
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(1, 3, 2);
+        graph.add_edge(2, 1, 1);
+        graph.add_edge(2, 3, 3);
+        graph.add_edge(2, 4, 1);
+        graph.add_edge(3, 0, 7);
+        graph.add_edge(3, 4, 2);
+
+        let tree = graph.shortest_path_tree(&[0], None);
+        assert_eq!(tree.path_to(4), Some(vec![0, 1, 3, 4]));
+        assert_eq!(tree.path_to(0), Some(vec![0]));
+        assert_eq!(tree.path_to(5), None);
+        assert_eq!(tree.prev(2), Some(0));
+
+        // 多始点
+        let tree = graph.shortest_path_tree(&[0, 2], None);
+        assert_eq!(
+            tree.dists(),
+            &[Some(0), Some(1), Some(0), Some(3), Some(1), None]
+        );
+        assert_eq!(tree.path_to(4), Some(vec![2, 4]));
+
+        // 早期終了
+        let tree = graph.shortest_path_tree(&[0], Some(1));
+        assert_eq!(tree.dist(1), Some(1));
+        assert_eq!(tree.path_to(1), Some(vec![0, 1]));
+    }
+
+    #[test]
+    fn test_shortest_path_tree_signed_weight() {
+        let mut builder = GraphBuilder::undirected(4);
+        builder
+            .add_weighted_edge(0, 1, 5i64)
+            .add_weighted_edge(1, 2, 4)
+            .add_weighted_edge(0, 2, 10)
+            .add_weighted_edge(2, 3, 1);
+        let tree = shortest_path_tree::<OpAdd<i64>, _>(&builder.build(), &[3], None);
+        assert_eq!(tree.dists(), &[Some(10), Some(5), Some(1), Some(0)]);
+        assert_eq!(tree.path_to(0), Some(vec![3, 2, 1, 0]));
+    }
+
+    // (距離, 辺の数) の辞書順で比較する重み
+    #[derive(Default)]
+    struct OpLexAdd;
+
+    impl Magma for OpLexAdd {
+        type Set = (u64, u64);
+
+        fn op(&self, x: Self::Set, y: Self::Set) -> Self::Set {
+            (x.0 + y.0, x.1 + y.1)
+        }
+    }
+
+    impl Associative for OpLexAdd {}
+
+    impl Identity for OpLexAdd {
+        fn id(&self) -> Self::Set {
+            (0, 0)
+        }
+    }
+
+    #[test]
+    fn test_shortest_path_tree_tuple_weight() {
+        // 距離が等しい経路のうち、辺の数が最小のものを選ぶ
+        let mut builder = GraphBuilder::directed(4);
+        for &(from, to, cost) in &[(0, 1, 1), (1, 2, 1), (2, 3, 1), (0, 3, 3), (1, 3, 2)] {
+            builder.add_weighted_edge(from, to, (cost, 1));
+        }
+        let tree = shortest_path_tree::<OpLexAdd, _>(&builder.build(), &[0], None);
+        assert_eq!(tree.dist(3), Some((3, 1)));
+        assert_eq!(tree.path_to(3), Some(vec![0, 3]));
+    }
+
+    #[test]
+    fn test_count_shortest_paths() {
+        #[derive(Copy, Clone, Eq, PartialEq)]
+        struct Mod1000000007;
+        impl Modulo for Mod1000000007 {
+            fn modulo() -> i64 {
+                1000000007
+            }
+        }
+        type F = FiniteField<Mod1000000007>;
+
+        // 2 x k の格子状のグラフで、各列で上下どちらを通るかを選べる
+        let k = 40;
+        let mut builder = GraphBuilder::directed(2 * k + 2);
+        for i in 0..k {
+            for a in 0..2 {
+                for b in 0..2 {
+                    builder.add_weighted_edge(2 * i + a, 2 * i + 2 + b, 1u64);
+                }
+            }
+        }
+        let (tree, count) = count_shortest_paths::<OpAdd<u64>, F, _>(&builder.build(), &[0, 1]);
+        assert_eq!(tree.dist(2 * k), Some(k as u64));
+        assert_eq!(count[2 * k], F::new(2).pow(k as u32));
+        assert_eq!(count[0], F::new(1));
+
+        let mut builder = GraphBuilder::directed(4);
+        builder
+            .add_weighted_edge(0, 1, 1u64)
+            .add_weighted_edge(0, 2, 1)
+            .add_weighted_edge(1, 3, 1)
+            .add_weighted_edge(2, 3, 2);
+        let (_, count) = count_shortest_paths::<OpAdd<u64>, u64, _>(&builder.build(), &[0]);
+        assert_eq!(count, vec![1, 1, 1, 1]);
     }
 }
@@ -0,0 +1,396 @@
+//! Dinic 法および push-relabel 法による最大流。
+//!
+//! `ford_fulkerson` と同じ形で辺を追加し、流量を `Edge` として取得できる。
+use std::collections::VecDeque;
+
+use super::ford_fulkerson::Edge;
+use super::static_graph::Neighbors;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct GraphEdge {
+    to: usize,
+    cap: usize,
+    rev: usize,
+}
+
+/// 最大流を求めるためのフローネットワーク
+///
+/// 同じネットワークに対して `dinic` と `hlpp` のどちらを用いてもよく、
+/// 一度流した後に容量を変更して続けて流すこともできる。
+#[derive(Debug, Clone)]
+pub struct Graph {
+    n: usize,
+    data: Vec<Vec<GraphEdge>>,
+    edge_pos: Vec<(usize, usize)>,
+}
+
+impl Graph {
+    /// `n` 頂点で辺を持たないネットワークを作成する。
+    pub fn new(n: usize) -> Self {
+        Self {
+            n,
+            data: vec![Vec::new(); n],
+            edge_pos: vec![],
+        }
+    }
+
+    /// 辺の重みを容量とみなして、`graph` の各辺を追加したフローネットワークを作成する。
+    pub fn from_graph<G: Neighbors<Weight = usize>>(graph: &G) -> Self {
+        let mut res = Self::new(graph.node_count());
+        for from in 0..graph.node_count() {
+            for &(to, cap) in graph.neighbors(from) {
+                res.add_edge(from, to, cap);
+            }
+        }
+        res
+    }
+
+    /// `from` から `to` への容量 `cap` の辺を追加し、その辺の番号を返す。
+    pub fn add_edge(&mut self, from: usize, to: usize, cap: usize) -> usize {
+        let pos = self.data[from].len();
+        let rev = self.data[to].len() + usize::from(from == to);
+        self.data[from].push(GraphEdge { to, cap, rev });
+        self.data[to].push(GraphEdge {
+            to: from,
+            cap: 0,
+            rev: pos,
+        });
+        self.edge_pos.push((from, pos));
+        self.edge_pos.len() - 1
+    }
+
+    /// `i` 番目に追加した辺の状態を返す。
+    pub fn edge(&self, i: usize) -> Edge {
+        let (from, pos) = self.edge_pos[i];
+        let edge = &self.data[from][pos];
+        let rev_edge = &self.data[edge.to][edge.rev];
+        Edge {
+            from,
+            to: edge.to,
+            cap: edge.cap + rev_edge.cap,
+            flow: rev_edge.cap,
+        }
+    }
+
+    /// 全ての辺の状態を、追加した順に返す。
+    pub fn edges(&self) -> Vec<Edge> {
+        (0..self.edge_pos.len()).map(|i| self.edge(i)).collect()
+    }
+
+    /// `i` 番目に追加した辺の容量を `new_cap`、流量を `new_flow` に変更する。
+    ///
+    /// 他の辺の流量は変更しないため、流量保存則が崩れうることに注意する。
+    pub fn change_edge(&mut self, i: usize, new_cap: usize, new_flow: usize) {
+        assert!(new_flow <= new_cap);
+        let (from, pos) = self.edge_pos[i];
+        let GraphEdge { to, rev, .. } = self.data[from][pos];
+        self.data[from][pos].cap = new_cap - new_flow;
+        self.data[to][rev].cap = new_flow;
+    }
+
+    fn push(&mut self, v: usize, i: usize, d: usize) {
+        let GraphEdge { to, rev, .. } = self.data[v][i];
+        self.data[v][i].cap -= d;
+        self.data[to][rev].cap += d;
+    }
+
+    // 残余ネットワーク上で start からの距離を求める
+    fn levels(&self, start: usize) -> Vec<Option<usize>> {
+        let mut level = vec![None; self.n];
+        let mut queue = VecDeque::new();
+        level[start] = Some(0);
+        queue.push_back(start);
+        while let Some(v) = queue.pop_front() {
+            for edge in &self.data[v] {
+                if edge.cap > 0 && level[edge.to].is_none() {
+                    level[edge.to] = level[v].map(|l| l + 1);
+                    queue.push_back(edge.to);
+                }
+            }
+        }
+        level
+    }
+
+    fn dinic_dfs(
+        &mut self,
+        level: &[Option<usize>],
+        iter: &mut [usize],
+        v: usize,
+        target: usize,
+        flow: usize,
+    ) -> usize {
+        if v == target {
+            return flow;
+        }
+        while iter[v] < self.data[v].len() {
+            let GraphEdge { to, cap, .. } = self.data[v][iter[v]];
+            if cap > 0 && level[to] == level[v].map(|l| l + 1) {
+                let d = self.dinic_dfs(level, iter, to, target, flow.min(cap));
+                if d > 0 {
+                    self.push(v, iter[v], d);
+                    return d;
+                }
+            }
+            iter[v] += 1;
+        }
+        0
+    }
+
+    /// Dinic 法で `start` から `target` への最大流を `O(N^2 M)` で流し、その流量を返す。
+    pub fn dinic(&mut self, start: usize, target: usize) -> usize {
+        assert_ne!(start, target);
+        let mut total_flow = 0;
+        loop {
+            let level = self.levels(start);
+            if level[target].is_none() {
+                break;
+            }
+            let mut iter = vec![0; self.n];
+            loop {
+                let flow = self.dinic_dfs(&level, &mut iter, start, target, std::usize::MAX);
+                if flow == 0 {
+                    break;
+                }
+                total_flow += flow;
+            }
+        }
+        total_flow
+    }
+
+    /// 最高ラベル優先の push-relabel 法 (HLPP) で `start` から `target` への最大流を
+    /// `O(N^2 √M)` で流し、その流量を返す。
+    pub fn hlpp(&mut self, start: usize, target: usize) -> usize {
+        assert_ne!(start, target);
+        let n = self.n;
+        // 高さの初期値は残余ネットワーク上の target までの距離とする
+        let mut height = vec![n; n];
+        let mut queue = VecDeque::new();
+        height[target] = 0;
+        queue.push_back(target);
+        while let Some(v) = queue.pop_front() {
+            for edge in &self.data[v] {
+                let u = edge.to;
+                if height[u] == n && u != start && self.data[u][edge.rev].cap > 0 {
+                    height[u] = height[v] + 1;
+                    queue.push_back(u);
+                }
+            }
+        }
+        height[start] = n;
+
+        let mut count = vec![0; 2 * n + 1];
+        for &h in &height {
+            count[h] += 1;
+        }
+        let mut excess = vec![0; n];
+        let mut iter = vec![0; n];
+        // 高さごとの余剰を持つ頂点
+        let mut buckets = vec![vec![]; 2 * n + 1];
+        let mut highest = 0;
+
+        for i in 0..self.data[start].len() {
+            let GraphEdge { to, cap, .. } = self.data[start][i];
+            if cap > 0 {
+                self.push(start, i, cap);
+                if excess[to] == 0 && to != target {
+                    buckets[height[to]].push(to);
+                    highest = highest.max(height[to]);
+                }
+                excess[to] += cap;
+            }
+        }
+
+        loop {
+            while highest > 0 && buckets[highest].is_empty() {
+                highest -= 1;
+            }
+            let v = match buckets[highest].pop() {
+                Some(v) => v,
+                None => break,
+            };
+            if v == start || v == target || height[v] != highest || excess[v] == 0 {
+                continue;
+            }
+            // v の余剰がなくなるまで押し出す
+            while excess[v] > 0 {
+                if iter[v] == self.data[v].len() {
+                    let old = height[v];
+                    height[v] = self.data[v]
+                        .iter()
+                        .filter(|e| e.cap > 0)
+                        .map(|e| height[e.to] + 1)
+                        .min()
+                        .unwrap()
+                        .min(2 * n);
+                    iter[v] = 0;
+                    count[old] -= 1;
+                    count[height[v]] += 1;
+                    if count[old] == 0 && old < n {
+                        // 高さ old の頂点がなくなると、それより高い頂点からは target に到達できない
+                        for u in 0..n {
+                            if old < height[u] && height[u] < n {
+                                count[height[u]] -= 1;
+                                height[u] = n + 1;
+                                count[n + 1] += 1;
+                                iter[u] = 0;
+                                if u != v && excess[u] > 0 {
+                                    buckets[n + 1].push(u);
+                                    highest = highest.max(n + 1);
+                                }
+                            }
+                        }
+                    }
+                    continue;
+                }
+                let GraphEdge { to, cap, .. } = self.data[v][iter[v]];
+                if cap > 0 && height[v] == height[to] + 1 {
+                    let d = excess[v].min(cap);
+                    self.push(v, iter[v], d);
+                    excess[v] -= d;
+                    if excess[to] == 0 && to != start && to != target {
+                        buckets[height[to]].push(to);
+                        highest = highest.max(height[to]);
+                    }
+                    excess[to] += d;
+                } else {
+                    iter[v] += 1;
+                }
+            }
+        }
+        excess[target]
+    }
+
+    /// 最大流を流した後に呼ぶことで、`start` 側の最小カットに含まれる頂点を昇順に返す。
+    pub fn min_cut(&self, start: usize) -> Vec<usize> {
+        let level = self.levels(start);
+        (0..self.n).filter(|&v| level[v].is_some()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::ford_fulkerson;
+    use crate::graph::static_graph::GraphBuilder;
+    use crate::utils::test_rng::XorShift;
+
+    // 流量保存則と容量制約を満たしているかを確かめ、start から流れ出る量を返す
+    fn check_flow(graph: &Graph, n: usize, start: usize, target: usize) -> usize {
+        let mut balance = vec![0i64; n];
+        for e in graph.edges() {
+            assert!(e.flow <= e.cap);
+            balance[e.from] -= e.flow as i64;
+            balance[e.to] += e.flow as i64;
+        }
+        for (v, &b) in balance.iter().enumerate() {
+            if v != start && v != target {
+                assert_eq!(b, 0);
+            }
+        }
+        (-balance[start]) as usize
+    }
+
+    fn sample() -> Graph {
+        let mut graph = Graph::new(5);
+        graph.add_edge(3, 0, 10);
+        graph.add_edge(3, 1, 2);
+        graph.add_edge(0, 1, 6);
+        graph.add_edge(0, 2, 6);
+        graph.add_edge(2, 1, 3);
+        graph.add_edge(2, 4, 8);
+        graph.add_edge(1, 4, 5);
+        graph
+    }
+
+    #[test]
+    fn test_dinic() {
+        let mut graph = sample();
+        assert_eq!(graph.dinic(3, 4), 11);
+        assert_eq!(check_flow(&graph, 5, 3, 4), 11);
+        assert_eq!(
+            graph.edge(0),
+            Edge {
+                from: 3,
+                to: 0,
+                cap: 10,
+                flow: 9
+            }
+        );
+        assert_eq!(graph.min_cut(3), vec![0, 1, 3]);
+
+        let mut graph = sample();
+        assert_eq!(graph.hlpp(3, 4), 11);
+        assert_eq!(check_flow(&graph, 5, 3, 4), 11);
+        assert_eq!(graph.min_cut(3), vec![0, 1, 3]);
+    }
+
+    #[test]
+    fn test_change_edge() {
+        let mut graph = sample();
+        assert_eq!(graph.dinic(3, 4), 11);
+        // 1 -> 4 の容量を増やすと、増やした分だけ追加で流せる
+        let e = graph.edge(6);
+        graph.change_edge(6, 8, e.flow);
+        assert_eq!(graph.dinic(3, 4), 1);
+        assert_eq!(check_flow(&graph, 5, 3, 4), 12);
+        assert_eq!(graph.min_cut(3), vec![3]);
+
+        graph.change_edge(0, 0, 0);
+        assert_eq!(graph.edge(0).flow, 0);
+    }
+
+    #[test]
+    fn test_dinic_static_graph() {
+        let mut builder = GraphBuilder::directed(4);
+        builder
+            .add_weighted_edge(0, 1, 2)
+            .add_weighted_edge(0, 2, 3)
+            .add_weighted_edge(1, 3, 4)
+            .add_weighted_edge(2, 3, 1)
+            .add_weighted_edge(2, 1, 5);
+        let mut graph = Graph::from_graph(&builder.build());
+        assert_eq!(graph.dinic(0, 3), 5);
+        assert_eq!(graph.min_cut(0), vec![0]);
+    }
+
+    #[test]
+    fn test_max_flow_random() {
+        let mut rng = XorShift::default();
+        for _ in 0..200 {
+            let n = rng.next() as usize % 8 + 2;
+            let m = rng.next() as usize % 30;
+            let edges: Vec<_> = (0..m)
+                .map(|_| {
+                    let from = rng.next() as usize % n;
+                    let to = rng.next() as usize % n;
+                    (from, to, rng.next() as usize % 20)
+                })
+                .collect();
+            let start = rng.next() as usize % n;
+            let target = (start + 1 + rng.next() as usize % (n - 1)) % n;
+
+            let mut ff = ford_fulkerson::Graph::new(n);
+            let mut dinic = Graph::new(n);
+            for &(from, to, cap) in &edges {
+                ff.add_edge(from, to, cap);
+                dinic.add_edge(from, to, cap);
+            }
+            let mut hlpp = dinic.clone();
+            let expected = ff.ford_fulkerson(start, target);
+            assert_eq!(dinic.dinic(start, target), expected);
+            assert_eq!(hlpp.hlpp(start, target), expected);
+            for &graph in &[&dinic, &hlpp] {
+                assert_eq!(check_flow(graph, n, start, target), expected);
+                // 最小カットの容量は最大流と等しい
+                let cut = graph.min_cut(start);
+                assert!(!cut.contains(&target));
+                let cap: usize = edges
+                    .iter()
+                    .filter(|&&(from, to, _)| cut.contains(&from) && !cut.contains(&to))
+                    .map(|&(_, _, cap)| cap)
+                    .sum();
+                assert_eq!(cap, expected);
+            }
+        }
+    }
+}
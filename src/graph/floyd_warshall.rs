@@ -0,0 +1,159 @@
+//! Floyd-Warshall 法による全点対最短経路。
+use super::static_graph::Neighbors;
+use crate::algebra::structure::Monoid;
+
+/// 距離行列。到達できない組の距離は `None` とする。
+pub type DistMatrix<T> = Vec<Vec<Option<T>>>;
+
+/// グラフの隣接行列を作成する。
+///
+/// `(i, j)` 成分は `i` から `j` への辺の重みの最小値で、辺がない場合は `None` となる。
+pub fn adjacency_matrix<M, G>(graph: &G) -> DistMatrix<M::Set>
+where
+    M: Monoid,
+    M::Set: Ord + Copy,
+    G: Neighbors<Weight = M::Set>,
+{
+    let n = graph.node_count();
+    let mut mat = vec![vec![None; n]; n];
+    for (v, row) in mat.iter_mut().enumerate() {
+        for &(u, w) in graph.neighbors(v) {
+            if row[u].map_or(true, |x| w < x) {
+                row[u] = Some(w);
+            }
+        }
+    }
+    mat
+}
+
+/// 隣接行列 `mat` から全点対最短距離を `O(N^3)` で求める。
+///
+/// `mat[i][j]` は `i` から `j` への辺の重みで、辺がない場合は `None` とする。
+/// 到達できない組の距離は `None` となる。
+/// 負閉路がある場合は、負閉路から到達できる頂点 (いずれかの頂点からの最短距離が負の無限大となる頂点) を昇順に並べて `Err` で返す。
+pub fn floyd_warshall<M>(mat: &[Vec<Option<M::Set>>]) -> Result<DistMatrix<M::Set>, Vec<usize>>
+where
+    M: Monoid,
+    M::Set: Ord + Copy,
+{
+    let monoid = M::default();
+    let n = mat.len();
+    let mut dist = mat.to_vec();
+    for (i, row) in dist.iter_mut().enumerate() {
+        if row[i].map_or(true, |x| monoid.id() < x) {
+            row[i] = Some(monoid.id());
+        }
+    }
+    // 負閉路を周回する経路の距離は指数的に小さくなりオーバーフローするため、負の無限大となる組は値とは別に管理する
+    let mut neg = vec![vec![false; n]; n];
+    for k in 0..n {
+        let cycle = neg[k][k] || dist[k][k].map_or(false, |x| x < monoid.id());
+        let row_k = dist[k].clone();
+        let neg_k = neg[k].clone();
+        for (row, neg_row) in dist.iter_mut().zip(neg.iter_mut()) {
+            let ik = match row[k] {
+                Some(ik) => ik,
+                None => continue,
+            };
+            let ik_neg = cycle || neg_row[k];
+            for ((x, x_neg), (kj, &kj_neg)) in row
+                .iter_mut()
+                .zip(neg_row.iter_mut())
+                .zip(row_k.iter().zip(&neg_k))
+            {
+                if let Some(kj) = *kj {
+                    if ik_neg || kj_neg {
+                        // 到達可能であることだけを残す
+                        x.get_or_insert(monoid.id());
+                        *x_neg = true;
+                    } else {
+                        let next = monoid.op(ik, kj);
+                        if x.map_or(true, |x| next < x) {
+                            *x = Some(next);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    let negative: Vec<_> = (0..n).filter(|&j| (0..n).any(|i| neg[i][j])).collect();
+    if negative.is_empty() {
+        Ok(dist)
+    } else {
+        Err(negative)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algebra::op_add::OpAdd;
+    use crate::graph::bellman_ford::bellman_ford;
+    use crate::graph::static_graph::{GraphBuilder, StaticGraph};
+    use crate::graph::test_graph::random_potential_edges;
+    use crate::utils::test_rng::XorShift;
+
+    #[test]
+    fn test_floyd_warshall() {
+        let mut builder = GraphBuilder::directed(4);
+        builder
+            .add_weighted_edge(0, 1, 1i64)
+            .add_weighted_edge(0, 2, 5)
+            .add_weighted_edge(1, 2, 2)
+            .add_weighted_edge(1, 3, 4)
+            .add_weighted_edge(2, 3, 1)
+            .add_weighted_edge(3, 2, 7)
+            .add_weighted_edge(0, 1, 3);
+        let mat = adjacency_matrix::<OpAdd<i64>, _>(&builder.build());
+        assert_eq!(mat[0][1], Some(1));
+        assert_eq!(
+            floyd_warshall::<OpAdd<i64>>(&mat),
+            Ok(vec![
+                vec![Some(0), Some(1), Some(3), Some(4)],
+                vec![None, Some(0), Some(2), Some(3)],
+                vec![None, None, Some(0), Some(1)],
+                vec![None, None, Some(7), Some(0)],
+            ])
+        );
+    }
+
+    #[test]
+    fn test_floyd_warshall_negative_cycle() {
+        let mut builder = GraphBuilder::directed(4);
+        builder
+            .add_weighted_edge(0, 1, 1i64)
+            .add_weighted_edge(1, 2, -1)
+            .add_weighted_edge(2, 1, -1)
+            .add_weighted_edge(2, 3, 0);
+        let mat = adjacency_matrix::<OpAdd<i64>, _>(&builder.build());
+        assert_eq!(floyd_warshall::<OpAdd<i64>>(&mat), Err(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn test_floyd_warshall_negative_cycle_overflow() {
+        // 負閉路を何周もする経路の距離がオーバーフローしないことを確かめる
+        let n = 64;
+        let mut builder = GraphBuilder::directed(n);
+        for i in 0..n {
+            for j in 0..n {
+                builder.add_weighted_edge(i, j, -1i64);
+            }
+        }
+        let mat = adjacency_matrix::<OpAdd<i64>, _>(&builder.build());
+        assert_eq!(floyd_warshall::<OpAdd<i64>>(&mat), Err((0..n).collect()));
+    }
+
+    #[test]
+    fn test_floyd_warshall_random() {
+        let mut rng = XorShift::default();
+        for _ in 0..100 {
+            let (n, edges) = random_potential_edges(&mut rng);
+            let graph = StaticGraph::from_edges(n, &edges);
+            let dist = floyd_warshall::<OpAdd<i64>>(&adjacency_matrix::<OpAdd<i64>, _>(&graph));
+            let expected: Vec<_> = (0..n)
+                .map(|s| bellman_ford::<OpAdd<i64>, _>(&graph, &[s]).unwrap())
+                .collect();
+            assert_eq!(dist, Ok(expected));
+        }
+    }
+}
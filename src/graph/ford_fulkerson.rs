@@ -1,3 +1,5 @@
+use super::static_graph::Neighbors;
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 struct GraphEdge {
     to: usize,
@@ -41,6 +43,17 @@ impl Graph {
         self.edge_pos.push((from, rev));
     }
 
+    /// 辺の重みを容量とみなして、`graph` の各辺を追加したフローネットワークを作成する。
+    pub fn from_graph<G: Neighbors<Weight = usize>>(graph: &G) -> Self {
+        let mut res = Self::new(graph.node_count());
+        for from in 0..graph.node_count() {
+            for &(to, cap) in graph.neighbors(from) {
+                res.add_edge(from, to, cap);
+            }
+        }
+        res
+    }
+
     fn dfs(&mut self, used: &mut Vec<bool>, node: usize, target: usize, flow: usize) -> usize {
         if node == target {
             return flow;
@@ -100,6 +113,7 @@ impl Graph {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::graph::static_graph::GraphBuilder;
 
     #[test]
     fn test_ford_fulkerson() {
@@ -160,4 +174,20 @@ mod tests {
             ]
         )
     }
+
+    #[test]
+    fn test_ford_fulkerson_static_graph() {
+        let mut builder = GraphBuilder::directed(5);
+        builder
+            .add_weighted_edge(3, 0, 10)
+            .add_weighted_edge(3, 1, 2)
+            .add_weighted_edge(0, 1, 6)
+            .add_weighted_edge(0, 2, 6)
+            .add_weighted_edge(2, 1, 3)
+            .add_weighted_edge(2, 4, 8)
+            .add_weighted_edge(1, 4, 5);
+        let mut graph = Graph::from_graph(&builder.build());
+        assert_eq!(graph.ford_fulkerson(3, 4), 11);
+        assert_eq!(graph.edges().len(), 7);
+    }
 }
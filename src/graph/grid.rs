@@ -0,0 +1,173 @@
+//! グリッド上の幅優先探索。
+use std::collections::VecDeque;
+
+/// グリッド上で隣接するマスの取り方
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighborhood {
+    /// 上下左右の 4 近傍
+    Four,
+    /// 斜めを含む 8 近傍
+    Eight,
+}
+
+impl Neighborhood {
+    fn dirs(self) -> &'static [(isize, isize)] {
+        match self {
+            Neighborhood::Four => &[(-1, 0), (0, -1), (0, 1), (1, 0)],
+            Neighborhood::Eight => &[
+                (-1, -1),
+                (-1, 0),
+                (-1, 1),
+                (0, -1),
+                (0, 1),
+                (1, -1),
+                (1, 0),
+                (1, 1),
+            ],
+        }
+    }
+}
+
+/// グリッド上の幅優先探索の結果
+#[derive(Debug, Clone)]
+pub struct GridBfs {
+    dist: Vec<Vec<Option<usize>>>,
+    prev: Vec<Vec<Option<(usize, usize)>>>,
+}
+
+impl GridBfs {
+    /// 迷路 `maze` の壁 `wall` 以外のマスを辿り、始点の集合 `sources` から各マスへの最短距離を `O(HW)` で求める。
+    ///
+    /// グラフを構築せずに、マスの座標 `(行, 列)` を直接扱う。
+    pub fn new(
+        maze: &[Vec<u8>],
+        wall: u8,
+        sources: &[(usize, usize)],
+        neighborhood: Neighborhood,
+    ) -> Self {
+        let mut dist: Vec<Vec<Option<usize>>> =
+            maze.iter().map(|row| vec![None; row.len()]).collect();
+        let mut prev = maze
+            .iter()
+            .map(|row| vec![None; row.len()])
+            .collect::<Vec<_>>();
+        let mut queue = VecDeque::new();
+        for &(i, j) in sources {
+            if maze[i][j] != wall && dist[i][j].is_none() {
+                dist[i][j] = Some(0);
+                queue.push_back((i, j));
+            }
+        }
+        while let Some((i, j)) = queue.pop_front() {
+            let d = dist[i][j].unwrap();
+            for &(di, dj) in neighborhood.dirs() {
+                let (ni, nj) = (i as isize + di, j as isize + dj);
+                if ni < 0 || nj < 0 {
+                    continue;
+                }
+                let (ni, nj) = (ni as usize, nj as usize);
+                if maze
+                    .get(ni)
+                    .and_then(|row| row.get(nj))
+                    .map_or(true, |&c| c == wall)
+                {
+                    continue;
+                }
+                if dist[ni][nj].is_none() {
+                    dist[ni][nj] = Some(d + 1);
+                    prev[ni][nj] = Some((i, j));
+                    queue.push_back((ni, nj));
+                }
+            }
+        }
+        Self { dist, prev }
+    }
+
+    /// マス `(i, j)` への最短距離を返す。到達できない場合は `None` を返す。
+    pub fn dist(&self, (i, j): (usize, usize)) -> Option<usize> {
+        self.dist[i][j]
+    }
+
+    /// 全てのマスへの最短距離を返す。
+    pub fn dists(&self) -> &[Vec<Option<usize>>] {
+        &self.dist
+    }
+
+    /// 始点のいずれかから `target` までの最短経路を、通るマスの列として返す。
+    pub fn path_to(&self, target: (usize, usize)) -> Option<Vec<(usize, usize)>> {
+        self.dist(target)?;
+        let mut path = vec![target];
+        let (mut i, mut j) = target;
+        while let Some(p) = self.prev[i][j] {
+            path.push(p);
+            let (pi, pj) = p;
+            i = pi;
+            j = pj;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(maze: &[&str]) -> Vec<Vec<u8>> {
+        maze.iter().map(|row| row.bytes().collect()).collect()
+    }
+
+    #[test]
+    fn test_grid_bfs_four() {
+        let maze = parse(&["..#..", ".##.#", "....#", "#.#.."]);
+        let bfs = GridBfs::new(&maze, b'#', &[(0, 0)], Neighborhood::Four);
+        assert_eq!(bfs.dist((0, 0)), Some(0));
+        assert_eq!(bfs.dist((3, 4)), Some(7));
+        assert_eq!(bfs.dist((0, 3)), Some(7));
+        assert_eq!(bfs.dist((0, 2)), None);
+        assert_eq!(bfs.dist((3, 0)), None);
+        assert_eq!(
+            bfs.path_to((3, 4)),
+            Some(vec![
+                (0, 0),
+                (1, 0),
+                (2, 0),
+                (2, 1),
+                (2, 2),
+                (2, 3),
+                (3, 3),
+                (3, 4)
+            ])
+        );
+        assert_eq!(bfs.path_to((0, 2)), None);
+    }
+
+    #[test]
+    fn test_grid_bfs_eight() {
+        let maze = parse(&[".#.", "#.#", ".#."]);
+        let bfs = GridBfs::new(&maze, b'#', &[(0, 0)], Neighborhood::Eight);
+        assert_eq!(
+            bfs.dists(),
+            &[
+                vec![Some(0), None, Some(2)],
+                vec![None, Some(1), None],
+                vec![Some(2), None, Some(2)],
+            ]
+        );
+        assert_eq!(bfs.path_to((2, 2)), Some(vec![(0, 0), (1, 1), (2, 2)]));
+
+        let bfs = GridBfs::new(&maze, b'#', &[(0, 0)], Neighborhood::Four);
+        assert_eq!(bfs.dist((1, 1)), None);
+    }
+
+    #[test]
+    fn test_grid_bfs_multi_source() {
+        let maze = parse(&["....."]);
+        let bfs = GridBfs::new(&maze, b'#', &[(0, 0), (0, 4)], Neighborhood::Four);
+        assert_eq!(
+            bfs.dists(),
+            &[vec![Some(0), Some(1), Some(2), Some(1), Some(0)]]
+        );
+        assert_eq!(bfs.path_to((0, 3)), Some(vec![(0, 4), (0, 3)]));
+    }
+}
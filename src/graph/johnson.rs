@@ -0,0 +1,99 @@
+//! Johnson 法による全点対最短経路。
+use super::bellman_ford::bellman_ford;
+use super::dijkstra::shortest_path_tree;
+use super::floyd_warshall::DistMatrix;
+use super::static_graph::{Neighbors, StaticGraph};
+use crate::algebra::structure::CommutativeGroup;
+
+/// 負の辺を含む疎なグラフの全点対最短距離を `O(NM log M)` で求める。
+///
+/// Bellman-Ford 法で求めたポテンシャル `h` で辺 `(u, v, w)` の重みを `w + h(u) - h(v)` (非負) に変換し、
+/// 各頂点から Dijkstra 法を行う。重みの差を取るため、`M` は可換群とする。
+///
+/// 到達できない組の距離は `None` となる。
+/// 負閉路がある場合は、負閉路から到達できる頂点 (いずれかの頂点からの最短距離が負の無限大となる頂点) を昇順に並べて `Err` で返す。
+pub fn johnson<M, G>(graph: &G) -> Result<DistMatrix<M::Set>, Vec<usize>>
+where
+    M: CommutativeGroup,
+    M::Set: Ord + Copy,
+    G: Neighbors<Weight = M::Set>,
+{
+    let monoid = M::default();
+    let n = graph.node_count();
+    // 全ての頂点に重み 0 の辺を張った仮想的な始点からの距離をポテンシャルとする
+    let sources: Vec<_> = (0..n).collect();
+    let h: Vec<_> = bellman_ford::<M, G>(graph, &sources)?
+        .into_iter()
+        .map(Option::unwrap)
+        .collect();
+    let mut edges = vec![];
+    for v in 0..n {
+        for &(u, w) in graph.neighbors(v) {
+            edges.push((v, u, monoid.op(monoid.op(w, h[v]), monoid.recip(h[u]))));
+        }
+    }
+    let reweighted = StaticGraph::from_edges(n, &edges);
+    let dist = (0..n)
+        .map(|s| {
+            let tree = shortest_path_tree::<M, _>(&reweighted, &[s], None);
+            (0..n)
+                .map(|t| {
+                    tree.dist(t)
+                        .map(|d| monoid.op(monoid.op(d, monoid.recip(h[s])), h[t]))
+                })
+                .collect()
+        })
+        .collect();
+    Ok(dist)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algebra::op_add::OpAdd;
+    use crate::graph::floyd_warshall::{adjacency_matrix, floyd_warshall};
+    use crate::graph::static_graph::GraphBuilder;
+    use crate::graph::test_graph::random_potential_edges;
+    use crate::utils::test_rng::XorShift;
+
+    #[test]
+    fn test_johnson() {
+        let mut builder = GraphBuilder::directed(4);
+        builder
+            .add_weighted_edge(0, 1, -2i64)
+            .add_weighted_edge(1, 2, 3)
+            .add_weighted_edge(0, 2, 2)
+            .add_weighted_edge(2, 3, -4)
+            .add_weighted_edge(3, 1, 2);
+        assert_eq!(
+            johnson::<OpAdd<i64>, _>(&builder.build()),
+            Ok(vec![
+                vec![Some(0), Some(-2), Some(1), Some(-3)],
+                vec![None, Some(0), Some(3), Some(-1)],
+                vec![None, Some(-2), Some(0), Some(-4)],
+                vec![None, Some(2), Some(5), Some(0)],
+            ])
+        );
+
+        builder.add_weighted_edge(3, 2, 3);
+        let graph = builder.build();
+        assert_eq!(johnson::<OpAdd<i64>, _>(&graph), Err(vec![1, 2, 3]));
+        assert_eq!(
+            floyd_warshall::<OpAdd<i64>>(&adjacency_matrix::<OpAdd<i64>, _>(&graph)),
+            Err(vec![1, 2, 3])
+        );
+    }
+
+    #[test]
+    fn test_johnson_random() {
+        let mut rng = XorShift::default();
+        for _ in 0..100 {
+            let (n, edges) = random_potential_edges(&mut rng);
+            let graph = StaticGraph::from_edges(n, &edges);
+            assert_eq!(
+                johnson::<OpAdd<i64>, _>(&graph),
+                floyd_warshall::<OpAdd<i64>>(&adjacency_matrix::<OpAdd<i64>, _>(&graph))
+            );
+        }
+    }
+}
@@ -1,8 +1,9 @@
+use super::static_graph::Neighbors;
+
 #[derive(Debug, Clone)]
 pub struct SccGraph {
     len: usize,
-    data: Vec<Vec<usize>>,
-    rev_data: Vec<Vec<usize>>,
+    data: Vec<Vec<(usize, ())>>,
 }
 
 impl SccGraph {
@@ -10,59 +11,82 @@ impl SccGraph {
         Self {
             len: n,
             data: vec![vec![]; n],
-            rev_data: vec![vec![]; n],
         }
     }
 
     pub fn add_edge(&mut self, from: usize, to: usize) {
-        self.data[from].push(to);
-        self.rev_data[to].push(from);
+        self.data[from].push((to, ()));
+    }
+
+    pub fn scc(&self) -> Vec<Vec<usize>> {
+        scc(self)
     }
+}
+
+impl Neighbors for SccGraph {
+    type Weight = ();
+
+    fn node_count(&self) -> usize {
+        self.len
+    }
+
+    fn neighbors(&self, v: usize) -> &[(usize, ())] {
+        &self.data[v]
+    }
+}
 
-    fn dfs(&self, v: usize, used: &mut Vec<bool>, vs: &mut Vec<usize>) {
-        used[v] = true;
-        for u in &self.data[v] {
-            if !used[*u] {
-                self.dfs(*u, used, vs);
-            }
+fn dfs<G: Neighbors>(graph: &G, v: usize, used: &mut Vec<bool>, vs: &mut Vec<usize>) {
+    used[v] = true;
+    for &(u, _) in graph.neighbors(v) {
+        if !used[u] {
+            dfs(graph, u, used, vs);
         }
-        vs.push(v);
     }
+    vs.push(v);
+}
 
-    fn rdfs(&self, v: usize, used: &mut Vec<bool>, cmp: &mut Vec<usize>) {
-        used[v] = true;
-        cmp.push(v);
-        for u in &self.rev_data[v] {
-            if !used[*u] {
-                self.rdfs(*u, used, cmp);
-            }
+fn rdfs(rev_data: &[Vec<usize>], v: usize, used: &mut Vec<bool>, cmp: &mut Vec<usize>) {
+    used[v] = true;
+    cmp.push(v);
+    for &u in &rev_data[v] {
+        if !used[u] {
+            rdfs(rev_data, u, used, cmp);
         }
     }
+}
 
-    pub fn scc(&self) -> Vec<Vec<usize>> {
-        let mut used = vec![false; self.len];
-        let mut vs = vec![];
-        for v in 0..self.len {
-            if !used[v] {
-                self.dfs(v, &mut used, &mut vs);
-            }
+/// 強連結成分分解を行い、トポロジカル順に並べた各成分の頂点を返す。
+pub fn scc<G: Neighbors>(graph: &G) -> Vec<Vec<usize>> {
+    let n = graph.node_count();
+    let mut rev_data = vec![vec![]; n];
+    for v in 0..n {
+        for &(u, _) in graph.neighbors(v) {
+            rev_data[u].push(v);
+        }
+    }
+    let mut used = vec![false; n];
+    let mut vs = vec![];
+    for v in 0..n {
+        if !used[v] {
+            dfs(graph, v, &mut used, &mut vs);
         }
-        let mut group = Vec::<Vec<usize>>::new();
-        let mut used = vec![false; self.len];
-        for v in vs.iter().rev() {
-            if !used[*v] {
-                let mut cmp = vec![];
-                self.rdfs(*v, &mut used, &mut cmp);
-                group.push(cmp);
-            }
+    }
+    let mut group = Vec::<Vec<usize>>::new();
+    let mut used = vec![false; n];
+    for v in vs.iter().rev() {
+        if !used[*v] {
+            let mut cmp = vec![];
+            rdfs(&rev_data, *v, &mut used, &mut cmp);
+            group.push(cmp);
         }
-        group
     }
+    group
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::graph::static_graph::GraphBuilder;
 
     #[test]
     fn test_scc() {
@@ -76,4 +100,16 @@ mod tests {
         graph.add_edge(4, 2);
         assert_eq!(graph.scc(), vec![vec![5], vec![1, 4], vec![2], vec![0, 3]]);
     }
+
+    #[test]
+    fn test_scc_static_graph() {
+        let mut builder = GraphBuilder::directed(6);
+        for &(from, to) in &[(1, 4), (5, 2), (3, 0), (5, 5), (4, 1), (0, 3), (4, 2)] {
+            builder.add_edge(from, to);
+        }
+        assert_eq!(
+            scc(&builder.build()),
+            vec![vec![5], vec![1, 4], vec![2], vec![0, 3]]
+        );
+    }
 }
@@ -0,0 +1,170 @@
+//! グラフの共通の表現。
+use cargo_snippet::snippet;
+
+/// 各頂点に隣接する頂点を列挙できるグラフ
+///
+/// 頂点は `0, ..., node_count() - 1` で表し、
+/// 辺の重みを持たないグラフでは `Weight = ()` とする。
+#[snippet("graph/static_graph")]
+pub trait Neighbors {
+    type Weight: Copy;
+
+    /// 頂点数を返す。
+    fn node_count(&self) -> usize;
+
+    /// 頂点 `v` から出る辺の `(行き先, 重み)` を返す。
+    fn neighbors(&self, v: usize) -> &[(usize, Self::Weight)];
+}
+
+#[snippet("graph/static_graph")]
+impl<W: Copy> Neighbors for Vec<Vec<(usize, W)>> {
+    type Weight = W;
+
+    fn node_count(&self) -> usize {
+        self.len()
+    }
+
+    fn neighbors(&self, v: usize) -> &[(usize, W)] {
+        &self[v]
+    }
+}
+
+/// CSR (Compressed Sparse Row) 形式で辺を持つ静的なグラフ
+#[snippet("graph/static_graph")]
+#[derive(Debug, Clone)]
+pub struct StaticGraph<W> {
+    start: Vec<usize>,
+    edges: Vec<(usize, W)>,
+}
+
+#[snippet("graph/static_graph")]
+impl<W: Copy> StaticGraph<W> {
+    /// 頂点数 `n`、有向辺 `(from, to, weight)` の列からグラフを作成する。
+    pub fn from_edges(n: usize, edges: &[(usize, usize, W)]) -> Self {
+        let mut start = vec![0; n + 1];
+        for &(from, _, _) in edges {
+            start[from + 1] += 1;
+        }
+        for i in 0..n {
+            start[i + 1] += start[i];
+        }
+        // 各辺が CSR 上で何番目に置かれるかを求める (同じ始点の辺は追加順)
+        let mut pos = start.clone();
+        let mut order = vec![0; edges.len()];
+        for (i, &(from, _, _)) in edges.iter().enumerate() {
+            order[pos[from]] = i;
+            pos[from] += 1;
+        }
+        let edges = order
+            .into_iter()
+            .map(|i| (edges[i].1, edges[i].2))
+            .collect();
+        Self { start, edges }
+    }
+
+    /// 辺の数を返す。
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+}
+
+#[snippet("graph/static_graph")]
+impl<W: Copy> Neighbors for StaticGraph<W> {
+    type Weight = W;
+
+    fn node_count(&self) -> usize {
+        self.start.len() - 1
+    }
+
+    fn neighbors(&self, v: usize) -> &[(usize, W)] {
+        &self.edges[self.start[v]..self.start[v + 1]]
+    }
+}
+
+/// `StaticGraph` を作成するためのビルダー
+#[snippet("graph/static_graph")]
+#[derive(Debug, Clone)]
+pub struct GraphBuilder<W> {
+    n: usize,
+    directed: bool,
+    edges: Vec<(usize, usize, W)>,
+}
+
+#[snippet("graph/static_graph")]
+impl<W: Copy> GraphBuilder<W> {
+    /// `n` 頂点の有向グラフのビルダーを作成する。
+    pub fn directed(n: usize) -> Self {
+        Self {
+            n,
+            directed: true,
+            edges: Vec::new(),
+        }
+    }
+
+    /// `n` 頂点の無向グラフのビルダーを作成する。
+    pub fn undirected(n: usize) -> Self {
+        Self {
+            n,
+            directed: false,
+            edges: Vec::new(),
+        }
+    }
+
+    /// 重み `w` の辺 `(from, to)` を追加する。
+    pub fn add_weighted_edge(&mut self, from: usize, to: usize, w: W) -> &mut Self {
+        assert!(from < self.n && to < self.n);
+        self.edges.push((from, to, w));
+        if !self.directed {
+            self.edges.push((to, from, w));
+        }
+        self
+    }
+
+    /// グラフを作成する。
+    pub fn build(&self) -> StaticGraph<W> {
+        StaticGraph::from_edges(self.n, &self.edges)
+    }
+}
+
+#[snippet("graph/static_graph")]
+impl GraphBuilder<()> {
+    /// 重みを持たない辺 `(from, to)` を追加する。
+    pub fn add_edge(&mut self, from: usize, to: usize) -> &mut Self {
+        self.add_weighted_edge(from, to, ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_static_graph() {
+        let mut builder = GraphBuilder::directed(4);
+        builder
+            .add_weighted_edge(2, 0, 5)
+            .add_weighted_edge(0, 1, 3)
+            .add_weighted_edge(2, 3, 1)
+            .add_weighted_edge(0, 2, 4);
+        let g = builder.build();
+        assert_eq!(g.node_count(), 4);
+        assert_eq!(g.edge_count(), 4);
+        assert_eq!(g.neighbors(0), &[(1, 3), (2, 4)]);
+        assert_eq!(g.neighbors(1), &[]);
+        assert_eq!(g.neighbors(2), &[(0, 5), (3, 1)]);
+        assert_eq!(g.neighbors(3), &[]);
+    }
+
+    #[test]
+    fn test_static_graph_undirected() {
+        let mut builder = GraphBuilder::undirected(3);
+        builder.add_edge(0, 1).add_edge(1, 2);
+        let g = builder.build();
+        assert_eq!(g.edge_count(), 4);
+        assert_eq!(g.neighbors(1), &[(0, ()), (2, ())]);
+        let adj: Vec<Vec<(usize, ())>> = vec![vec![(1, ())], vec![(0, ()), (2, ())], vec![(1, ())]];
+        for v in 0..3 {
+            assert_eq!(adj.neighbors(v), g.neighbors(v));
+        }
+    }
+}
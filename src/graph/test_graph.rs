@@ -0,0 +1,20 @@
+//! テスト用のランダムなグラフの生成。
+use crate::utils::test_rng::XorShift;
+
+/// 負閉路を持たず、負の辺を含み得るランダムな有向グラフの頂点数と辺を返す。
+///
+/// 非負の重みを頂点のポテンシャルでずらして負の辺を作る。
+pub fn random_potential_edges(rng: &mut XorShift) -> (usize, Vec<(usize, usize, i64)>) {
+    let n = rng.next() as usize % 8 + 1;
+    let m = rng.next() as usize % 20;
+    let p: Vec<_> = (0..n).map(|_| (rng.next() % 10) as i64).collect();
+    let edges = (0..m)
+        .map(|_| {
+            let from = rng.next() as usize % n;
+            let to = rng.next() as usize % n;
+            let w = (rng.next() % 10) as i64;
+            (from, to, w + p[from] - p[to])
+        })
+        .collect();
+    (n, edges)
+}
@@ -6,12 +6,14 @@ use std::fmt::{Debug, Display};
 #[snippet("math/mod_int")]
 use std::marker::PhantomData;
 #[snippet("math/mod_int")]
-use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
+use super::super::algebra::act_affine::ActAffine;
 use super::super::algebra::multiplicative::PartialMulRecip;
 use super::super::algebra::op_add::OpAdd;
 use super::super::algebra::op_mul::OpMul;
-use super::super::algebra::structure::{Associative, Commutative};
+use super::super::algebra::op_sized_add::OpSizedAdd;
+use super::super::algebra::structure::{Associative, Commutative, Identity, MonoidAction, Recip};
 
 use super::gcd::ext_gcd;
 
@@ -122,6 +124,14 @@ impl<M: Modulo> MulAssign for FiniteField<M> {
     }
 }
 
+#[snippet("math/mod_int")]
+impl<M: Modulo> Neg for FiniteField<M> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::new(-self.val())
+    }
+}
+
 impl<M: Modulo> Div for FiniteField<M> {
     type Output = Self;
     fn div(self, rhs: Self) -> Self {
@@ -165,6 +175,60 @@ impl<M: Modulo> Commutative for OpAdd<FiniteField<M>> {}
 impl<M: Modulo> Associative for OpMul<FiniteField<M>> {}
 #[snippet("math/mod_int")]
 impl<M: Modulo> Commutative for OpMul<FiniteField<M>> {}
+#[snippet("math/mod_int")]
+impl<M: Modulo + Copy> Identity for OpAdd<FiniteField<M>> {
+    fn id(&self) -> Self::Set {
+        FiniteField::new(0)
+    }
+}
+
+#[snippet("math/mod_int")]
+impl<M: Modulo + Copy> Identity for OpMul<FiniteField<M>> {
+    fn id(&self) -> Self::Set {
+        FiniteField::new(1)
+    }
+}
+
+/// 乗法の逆元。0 以外の元 (単元) のみを扱うことを仮定する。
+#[snippet("math/mod_int")]
+impl<M: Modulo + Copy> Recip for OpMul<FiniteField<M>> {
+    fn recip(&self, x: Self::Set) -> Self::Set {
+        match x.partial_mul_recip() {
+            Some(v) => v,
+            None => panic!("cannot devide by 0"),
+        }
+    }
+}
+
+#[snippet("math/mod_int")]
+impl<M: Modulo> Associative for OpSizedAdd<FiniteField<M>> {}
+#[snippet("math/mod_int")]
+impl<M: Modulo> Commutative for OpSizedAdd<FiniteField<M>> {}
+
+#[snippet("math/mod_int")]
+impl<M: Modulo> Identity for OpSizedAdd<FiniteField<M>> {
+    fn id(&self) -> Self::Set {
+        (FiniteField::new(0), 0)
+    }
+}
+
+#[snippet("math/mod_int")]
+impl<M: Modulo + Copy> Identity for ActAffine<FiniteField<M>> {
+    fn id(&self) -> Self::Set {
+        (FiniteField::new(1), FiniteField::new(0))
+    }
+}
+
+#[snippet("math/mod_int")]
+impl<M: Modulo + Copy> MonoidAction<OpSizedAdd<FiniteField<M>>> for ActAffine<FiniteField<M>> {
+    fn act(
+        &self,
+        x: (FiniteField<M>, usize),
+        f: (FiniteField<M>, FiniteField<M>),
+    ) -> (FiniteField<M>, usize) {
+        (f.0 * x.0 + f.1 * FiniteField::new(x.1 as i64), x.1)
+    }
+}
 
 /// mod を定義するためのマクロ。
 #[snippet("math/mod_int")]
@@ -1,3 +1,6 @@
 //! ユーティリティ用のモジュール。
 pub mod binary_search;
 pub mod integer;
+pub mod mo;
+#[cfg(test)]
+pub(crate) mod test_rng;
@@ -0,0 +1,530 @@
+//! Mo's algorithm に関するモジュール。
+use cargo_snippet::snippet;
+
+#[snippet("utils/mo")]
+use std::ops::Range;
+
+/// Mo's algorithm で管理する区間の状態
+///
+/// 区間 `[l, r)` に対して、
+/// - `add_left(l - 1)`, `add_right(r)`: 区間を一つ広げる
+/// - `remove_left(l)`, `remove_right(r - 1)`: 区間を一つ狭める
+///
+/// という形で呼び出される。
+#[snippet("utils/mo")]
+pub trait MoState {
+    type Output;
+
+    /// 区間の左端に要素 `i` を追加する。
+    fn add_left(&mut self, i: usize);
+    /// 区間の右端に要素 `i` を追加する。
+    fn add_right(&mut self, i: usize);
+    /// 区間の左端の要素 `i` を削除する。
+    fn remove_left(&mut self, i: usize);
+    /// 区間の右端の要素 `i` を削除する。
+    fn remove_right(&mut self, i: usize);
+    /// 現在の区間に対する答えを返す。
+    fn answer(&self) -> Self::Output;
+}
+
+/// 点更新を伴う Mo's algorithm で管理する区間の状態
+#[snippet("utils/mo")]
+pub trait MoUpdateState: MoState {
+    /// 現在の区間が `r` であるときに、`t` 番目の更新を適用する。
+    fn apply_update(&mut self, t: usize, r: Range<usize>);
+    /// 現在の区間が `r` であるときに、`t` 番目の更新を取り消す。
+    fn undo_update(&mut self, t: usize, r: Range<usize>);
+}
+
+/// 巻き戻しを用いる Mo's algorithm で管理する区間の状態
+///
+/// 要素の削除の代わりに、直前の `snapshot` の時点への巻き戻しを行う。
+#[snippet("utils/mo")]
+pub trait RollbackMoState {
+    type Output;
+
+    /// 区間の左端に要素 `i` を追加する。
+    fn add_left(&mut self, i: usize);
+    /// 区間の右端に要素 `i` を追加する。
+    fn add_right(&mut self, i: usize);
+    /// 現在の状態を保存する。
+    fn snapshot(&mut self);
+    /// 直前に `snapshot` を呼んだ時点の状態に戻す。
+    fn rollback(&mut self);
+    /// 区間が空である状態に戻す。
+    fn reset(&mut self);
+    /// 現在の区間に対する答えを返す。
+    fn answer(&self) -> Self::Output;
+}
+
+/// Hilbert 曲線上で点 `(x, y)` (`0 <= x, y < 2^log`) が何番目に現れるかを返す。
+#[snippet("utils/mo")]
+pub fn hilbert_order(x: usize, y: usize, log: usize) -> u64 {
+    let (mut x, mut y) = (x as u64, y as u64);
+    let n = 1u64 << log;
+    let mut d = 0;
+    let mut s = n >> 1;
+    while s > 0 {
+        let rx = (x & s > 0) as u64;
+        let ry = (y & s > 0) as u64;
+        d += s * s * ((3 * rx) ^ ry);
+        if ry == 0 {
+            if rx == 1 {
+                x = n - 1 - x;
+                y = n - 1 - y;
+            }
+            std::mem::swap(&mut x, &mut y);
+        }
+        s >>= 1;
+    }
+    d
+}
+
+/// 区間 `cur` を `next` まで動かす。
+#[snippet("utils/mo")]
+fn move_range<S: MoState>(state: &mut S, cur: &mut Range<usize>, next: &Range<usize>) {
+    while cur.start > next.start {
+        cur.start -= 1;
+        state.add_left(cur.start);
+    }
+    while cur.end < next.end {
+        state.add_right(cur.end);
+        cur.end += 1;
+    }
+    while cur.start < next.start {
+        state.remove_left(cur.start);
+        cur.start += 1;
+    }
+    while cur.end > next.end {
+        cur.end -= 1;
+        state.remove_right(cur.end);
+    }
+}
+
+/// Mo's algorithm
+///
+/// 長さ `N` の列に対する `Q` 個の区間クエリを、
+/// 区間の伸縮を `O(N sqrt Q)` 回行うことでまとめて処理する。
+#[snippet("utils/mo")]
+#[derive(Debug, Clone)]
+pub struct Mo {
+    n: usize,
+    queries: Vec<Range<usize>>,
+}
+
+#[snippet("utils/mo")]
+impl Mo {
+    /// 長さ `n` の列に対する Mo's algorithm を準備する。
+    pub fn new(n: usize) -> Self {
+        Self {
+            n,
+            queries: Vec::new(),
+        }
+    }
+
+    /// 区間 `r` に対するクエリを追加する。
+    pub fn add_query(&mut self, r: Range<usize>) {
+        assert!(r.start <= r.end && r.end <= self.n);
+        self.queries.push(r);
+    }
+
+    fn run_with_order<S: MoState>(&self, state: &mut S, order: Vec<usize>) -> Vec<S::Output> {
+        let mut res: Vec<Option<S::Output>> = (0..self.queries.len()).map(|_| None).collect();
+        let mut cur = 0..0;
+        for i in order {
+            move_range(state, &mut cur, &self.queries[i]);
+            res[i] = Some(state.answer());
+        }
+        res.into_iter().map(Option::unwrap).collect()
+    }
+
+    /// 各クエリの答えを、追加した順に返す。
+    pub fn run<S: MoState>(&self, state: &mut S) -> Vec<S::Output> {
+        let q = self.queries.len().max(1);
+        let block = (self.n / ((q as f64).sqrt() as usize).max(1)).max(1);
+        let mut order: Vec<usize> = (0..self.queries.len()).collect();
+        order.sort_by_key(|&i| {
+            let r = &self.queries[i];
+            let b = r.start / block;
+            // 偶奇で右端の向きを変える
+            let e = if b % 2 == 0 { r.end } else { self.n - r.end };
+            (b, e)
+        });
+        self.run_with_order(state, order)
+    }
+
+    /// Hilbert 曲線に沿った順に処理し、各クエリの答えを追加した順に返す。
+    pub fn run_hilbert<S: MoState>(&self, state: &mut S) -> Vec<S::Output> {
+        let mut log = 0;
+        while 1 << log <= self.n {
+            log += 1;
+        }
+        let mut order: Vec<usize> = (0..self.queries.len()).collect();
+        order.sort_by_cached_key(|&i| {
+            let r = &self.queries[i];
+            hilbert_order(r.start, r.end, log)
+        });
+        self.run_with_order(state, order)
+    }
+}
+
+/// 点更新を伴う Mo's algorithm
+///
+/// 長さ `N` の列に対する `Q` 個の区間クエリと点更新を、
+/// 時刻を三つ目の次元として `O(N^(5/3))` 程度の操作でまとめて処理する。
+#[snippet("utils/mo")]
+#[derive(Debug, Clone)]
+pub struct MoWithUpdates {
+    n: usize,
+    updates: usize,
+    // (区間, クエリ時点で適用済みの更新の数)
+    queries: Vec<(Range<usize>, usize)>,
+}
+
+#[snippet("utils/mo")]
+impl MoWithUpdates {
+    /// 長さ `n` の列に対する Mo's algorithm を準備する。
+    pub fn new(n: usize) -> Self {
+        Self {
+            n,
+            updates: 0,
+            queries: Vec::new(),
+        }
+    }
+
+    /// 更新を追加し、その番号を返す。
+    pub fn add_update(&mut self) -> usize {
+        self.updates += 1;
+        self.updates - 1
+    }
+
+    /// それまでに追加した更新を全て適用した列の区間 `r` に対するクエリを追加する。
+    pub fn add_query(&mut self, r: Range<usize>) {
+        assert!(r.start <= r.end && r.end <= self.n);
+        self.queries.push((r, self.updates));
+    }
+
+    /// 各クエリの答えを、追加した順に返す。
+    pub fn run<S: MoUpdateState>(&self, state: &mut S) -> Vec<S::Output> {
+        let block = ((self.n as f64).powf(2.0 / 3.0) as usize).max(1);
+        let mut order: Vec<usize> = (0..self.queries.len()).collect();
+        order.sort_by_key(|&i| {
+            let (r, t) = &self.queries[i];
+            (r.start / block, r.end / block, *t)
+        });
+        let mut res: Vec<Option<S::Output>> = (0..self.queries.len()).map(|_| None).collect();
+        let mut cur = 0..0;
+        let mut time = 0;
+        for i in order {
+            let (r, t) = &self.queries[i];
+            move_range(state, &mut cur, r);
+            while time < *t {
+                state.apply_update(time, cur.clone());
+                time += 1;
+            }
+            while time > *t {
+                time -= 1;
+                state.undo_update(time, cur.clone());
+            }
+            res[i] = Some(state.answer());
+        }
+        res.into_iter().map(Option::unwrap).collect()
+    }
+}
+
+/// 巻き戻しを用いる Mo's algorithm
+///
+/// 要素の削除が難しい場合に、追加と巻き戻しのみで区間クエリをまとめて処理する。
+#[snippet("utils/mo")]
+#[derive(Debug, Clone)]
+pub struct RollbackMo {
+    n: usize,
+    queries: Vec<Range<usize>>,
+}
+
+#[snippet("utils/mo")]
+impl RollbackMo {
+    /// 長さ `n` の列に対する Mo's algorithm を準備する。
+    pub fn new(n: usize) -> Self {
+        Self {
+            n,
+            queries: Vec::new(),
+        }
+    }
+
+    /// 区間 `r` に対するクエリを追加する。
+    pub fn add_query(&mut self, r: Range<usize>) {
+        assert!(r.start <= r.end && r.end <= self.n);
+        self.queries.push(r);
+    }
+
+    /// 各クエリの答えを、追加した順に返す。
+    pub fn run<S: RollbackMoState>(&self, state: &mut S) -> Vec<S::Output> {
+        let q = self.queries.len().max(1);
+        let block = (self.n / ((q as f64).sqrt() as usize).max(1)).max(1);
+        let mut order: Vec<usize> = (0..self.queries.len()).collect();
+        order.sort_by_key(|&i| {
+            let r = &self.queries[i];
+            (r.start / block, r.end)
+        });
+        let mut res: Vec<Option<S::Output>> = (0..self.queries.len()).map(|_| None).collect();
+        let mut cur_block = std::usize::MAX;
+        let mut r = 0;
+        for i in order {
+            let q = &self.queries[i];
+            let b = q.start / block;
+            // 左端が属するブロックの右端
+            let base = ((b + 1) * block).min(self.n);
+            if b != cur_block {
+                state.reset();
+                cur_block = b;
+                r = base;
+            }
+            if q.end <= base {
+                state.snapshot();
+                for j in q.clone() {
+                    state.add_right(j);
+                }
+                res[i] = Some(state.answer());
+                state.rollback();
+                continue;
+            }
+            while r < q.end {
+                state.add_right(r);
+                r += 1;
+            }
+            state.snapshot();
+            for j in (q.start..base).rev() {
+                state.add_left(j);
+            }
+            res[i] = Some(state.answer());
+            state.rollback();
+        }
+        res.into_iter().map(Option::unwrap).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_rng::XorShift;
+
+    /// 区間内の値の種類数
+    struct Distinct {
+        a: Vec<usize>,
+        cnt: Vec<usize>,
+        kinds: usize,
+        // (位置, 値) の更新。適用と取り消しはどちらも値の交換で行う
+        updates: Vec<(usize, usize)>,
+    }
+
+    impl Distinct {
+        fn new(a: Vec<usize>, updates: Vec<(usize, usize)>) -> Self {
+            Self {
+                a,
+                cnt: vec![0; 100],
+                kinds: 0,
+                updates,
+            }
+        }
+
+        fn add(&mut self, x: usize) {
+            self.cnt[x] += 1;
+            if self.cnt[x] == 1 {
+                self.kinds += 1;
+            }
+        }
+
+        fn remove(&mut self, x: usize) {
+            self.cnt[x] -= 1;
+            if self.cnt[x] == 0 {
+                self.kinds -= 1;
+            }
+        }
+
+        fn swap_update(&mut self, t: usize, r: Range<usize>) {
+            let (p, x) = self.updates[t];
+            if r.contains(&p) {
+                self.remove(self.a[p]);
+                self.add(x);
+            }
+            self.updates[t].1 = self.a[p];
+            self.a[p] = x;
+        }
+    }
+
+    impl MoState for Distinct {
+        type Output = usize;
+
+        fn add_left(&mut self, i: usize) {
+            self.add(self.a[i]);
+        }
+
+        fn add_right(&mut self, i: usize) {
+            self.add(self.a[i]);
+        }
+
+        fn remove_left(&mut self, i: usize) {
+            self.remove(self.a[i]);
+        }
+
+        fn remove_right(&mut self, i: usize) {
+            self.remove(self.a[i]);
+        }
+
+        fn answer(&self) -> usize {
+            self.kinds
+        }
+    }
+
+    impl MoUpdateState for Distinct {
+        fn apply_update(&mut self, t: usize, r: Range<usize>) {
+            self.swap_update(t, r);
+        }
+
+        fn undo_update(&mut self, t: usize, r: Range<usize>) {
+            self.swap_update(t, r);
+        }
+    }
+
+    fn naive_distinct(a: &[usize]) -> usize {
+        let mut b = a.to_vec();
+        b.sort_unstable();
+        b.dedup();
+        b.len()
+    }
+
+    fn random_range(rng: &mut XorShift, n: usize) -> Range<usize> {
+        let l = rng.next() as usize % (n + 1);
+        let r = rng.next() as usize % (n + 1);
+        l.min(r)..l.max(r)
+    }
+
+    #[test]
+    fn test_mo() {
+        let mut rng = XorShift::default();
+        let n = 100;
+        let a: Vec<usize> = (0..n).map(|_| rng.next() as usize % 20).collect();
+        let mut mo = Mo::new(n);
+        let mut expected = Vec::new();
+        for _ in 0..300 {
+            let r = random_range(&mut rng, n);
+            expected.push(naive_distinct(&a[r.clone()]));
+            mo.add_query(r);
+        }
+        assert_eq!(mo.run(&mut Distinct::new(a.clone(), vec![])), expected);
+        assert_eq!(mo.run_hilbert(&mut Distinct::new(a, vec![])), expected);
+    }
+
+    #[test]
+    fn test_mo_with_updates() {
+        let mut rng = XorShift::default();
+        let n = 100;
+        let init: Vec<usize> = (0..n).map(|_| rng.next() as usize % 20).collect();
+        let mut a = init.clone();
+        let mut mo = MoWithUpdates::new(n);
+        let mut updates = Vec::new();
+        let mut expected = Vec::new();
+        for _ in 0..300 {
+            if rng.next() % 2 == 0 {
+                let p = rng.next() as usize % n;
+                let x = rng.next() as usize % 20;
+                assert_eq!(mo.add_update(), updates.len());
+                updates.push((p, x));
+                a[p] = x;
+            } else {
+                let r = random_range(&mut rng, n);
+                expected.push(naive_distinct(&a[r.clone()]));
+                mo.add_query(r);
+            }
+        }
+        assert_eq!(mo.run(&mut Distinct::new(init, updates)), expected);
+    }
+
+    #[test]
+    fn test_rollback_mo() {
+        /// 区間内で最も多く現れる値の出現回数
+        struct Mode {
+            a: Vec<usize>,
+            cnt: Vec<usize>,
+            best: usize,
+            history: Vec<usize>,
+            saved: (usize, usize),
+        }
+
+        impl Mode {
+            fn add(&mut self, i: usize) {
+                let x = self.a[i];
+                self.cnt[x] += 1;
+                self.best = self.best.max(self.cnt[x]);
+                self.history.push(x);
+            }
+        }
+
+        impl RollbackMoState for Mode {
+            type Output = usize;
+
+            fn add_left(&mut self, i: usize) {
+                self.add(i);
+            }
+
+            fn add_right(&mut self, i: usize) {
+                self.add(i);
+            }
+
+            fn snapshot(&mut self) {
+                self.saved = (self.history.len(), self.best);
+            }
+
+            fn rollback(&mut self) {
+                while self.history.len() > self.saved.0 {
+                    let x = self.history.pop().unwrap();
+                    self.cnt[x] -= 1;
+                }
+                self.best = self.saved.1;
+            }
+
+            fn reset(&mut self) {
+                for x in self.history.drain(..) {
+                    self.cnt[x] -= 1;
+                }
+                self.best = 0;
+            }
+
+            fn answer(&self) -> usize {
+                self.best
+            }
+        }
+
+        let mut rng = XorShift::default();
+        let n = 100;
+        let a: Vec<usize> = (0..n).map(|_| rng.next() as usize % 10).collect();
+        let mut mo = RollbackMo::new(n);
+        let mut expected = Vec::new();
+        for _ in 0..300 {
+            let r = random_range(&mut rng, n);
+            let mut cnt = [0; 10];
+            a[r.clone()].iter().for_each(|&x| cnt[x] += 1);
+            expected.push(*cnt.iter().max().unwrap());
+            mo.add_query(r);
+        }
+        let mut state = Mode {
+            a,
+            cnt: vec![0; 10],
+            best: 0,
+            history: Vec::new(),
+            saved: (0, 0),
+        };
+        assert_eq!(mo.run(&mut state), expected);
+    }
+
+    #[test]
+    fn test_hilbert_order() {
+        // 2x2 の Hilbert 曲線は (0, 0), (0, 1), (1, 1), (1, 0) の順に通る
+        let order: Vec<_> = [(0, 0), (0, 1), (1, 1), (1, 0)]
+            .iter()
+            .map(|&(x, y)| hilbert_order(x, y, 1))
+            .collect();
+        assert_eq!(order, vec![0, 1, 2, 3]);
+    }
+}
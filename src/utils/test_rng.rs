@@ -0,0 +1,20 @@
+//! テスト用の疑似乱数生成器。
+
+/// xorshift による疑似乱数生成器
+pub struct XorShift(u64);
+
+impl Default for XorShift {
+    fn default() -> Self {
+        XorShift(88172645463325252)
+    }
+}
+
+impl XorShift {
+    /// 次の乱数を返す。
+    pub fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}